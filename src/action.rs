@@ -0,0 +1,80 @@
+// === Actions ===
+// What activating a texicon asks the app to do.
+// Menu files name them per item, e.g. `action: Settings`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub enum Action {
+    // Side and central menus
+    Experiments,
+    Timing,
+    Filtering,
+    Playground,
+    Gallery,
+    ThemeEditor,
+    Settings,
+    Documents,
+    // Top menu: the text size examples
+    TextUndersized,
+    TextNormal,
+    TextLarge,
+    TextExtraLarge,
+}
+
+// What an entry of a texicon's context menu asks the app to do with that texicon
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub enum ContextAction {
+    // Show the texicon's page, as a click on it in the side menu does
+    Open,
+    // Show its page in a new browser tab (web only)
+    OpenInNewTab,
+    // Add a copy of the texicon to the top menu
+    PinToTopMenu,
+    // Take a pinned texicon off the top menu again
+    Unpin,
+}
+
+// One entry of a texicon's context menu.
+// In menu files: `context: [(text: "Pin to top menu", action: PinToTopMenu)]`
+#[rustfmt::skip]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContextEntry {
+    pub text:   String,
+    pub action: ContextAction,
+}
+
+impl ContextEntry {
+    pub fn new(text: impl Into<String>, action: ContextAction) -> Self {
+        Self {
+            text: text.into(),
+            action,
+        }
+    }
+}
+
+// What happened to a texicon during a draw
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TexiEventKind {
+    // Clicked, or Enter/Space with the keyboard focus on it
+    Activated,
+    // After the `Activated` of the second click
+    DoubleClicked,
+    MiddleClicked,
+    HoverEntered,
+    HoverLeft,
+    // Right-click or long-press
+    ContextRequested,
+    // An entry of the context menu was picked
+    ContextChosen(ContextAction),
+    // Dragged along its menu from index `from`, to the event's item
+    Moved { from: usize },
+    // A pinned copy of a texicon dragged from another menu
+    DroppedIn,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TexiEvent {
+    pub kind: TexiEventKind,
+    // Index of the texicon in its menu
+    pub item: usize,
+    pub action: Option<Action>,
+}
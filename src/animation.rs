@@ -0,0 +1,148 @@
+use egui::emath::easing;
+
+use crate::palette::{Palette, Role};
+
+// === Constants ===
+#[rustfmt::skip] pub const MAX_DURATION: f32     = 1.0;
+#[rustfmt::skip] const DEFAULT_DURATION: f32     = 0.15;
+
+// The curve a transition follows, from 0 (start) to 1 (end)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    #[default]
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub const ALL: [Self; 4] = [Self::Linear, Self::EaseIn, Self::EaseOut, Self::EaseInOut];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::EaseIn => "Ease in",
+            Self::EaseOut => "Ease out",
+            Self::EaseInOut => "Ease in and out",
+        }
+    }
+
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Self::Linear => easing::linear(t),
+            Self::EaseIn => easing::quadratic_in(t),
+            Self::EaseOut => easing::quadratic_out(t),
+            Self::EaseInOut => easing::cubic_in_out(t),
+        }
+    }
+}
+
+// ----------
+// Animations
+// ----------
+// How texicons move between their normal, selected and hovered looks, and between themes.
+// Kept in the egui context so every menu animates the same way.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct AnimationSettings {
+    // Snap straight to the new look, as the texicon widget does by itself
+    pub reduce_motion: bool,
+    // In seconds
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            reduce_motion: false,
+            duration: DEFAULT_DURATION,
+            easing: Easing::default(),
+        }
+    }
+}
+
+// The palette a theme switch fades from and to
+#[derive(Clone, Copy)]
+struct PaletteTransition {
+    from: Palette,
+    to: Palette,
+    // Each transition animates its own value, from 0 to 1
+    generation: u64,
+}
+
+impl AnimationSettings {
+    fn id() -> egui::Id {
+        egui::Id::new("animation_settings")
+    }
+
+    pub fn get(ctx: &egui::Context) -> Self {
+        ctx.data(|d| d.get_temp(Self::id())).unwrap_or_default()
+    }
+
+    pub fn set(self, ctx: &egui::Context) {
+        ctx.data_mut(|d| d.insert_temp(Self::id(), self));
+    }
+
+    pub fn animates(self) -> bool {
+        !self.reduce_motion && self.duration > 0.0
+    }
+
+    // How far the value `id` has come towards `on`: 0 is off, 1 is on.
+    // Eased the same way in both directions.
+    pub fn blend(self, ctx: &egui::Context, id: egui::Id, on: bool) -> f32 {
+        let target = if on { 1.0 } else { 0.0 };
+        if !self.animates() {
+            return target;
+        }
+        let t = ctx.animate_value_with_time(id, target, self.duration);
+        if on {
+            self.easing.apply(t)
+        } else {
+            1.0 - self.easing.apply(1.0 - t)
+        }
+    }
+
+    // The palette to draw with: `target`, or on the way there after a theme switch
+    pub fn palette(self, ctx: &egui::Context, target: Palette) -> Palette {
+        if !self.animates() {
+            return target;
+        }
+        let id = egui::Id::new("palette_transition");
+        let progress = |transition: &PaletteTransition| {
+            let t = ctx.animate_value_with_time(id.with(transition.generation), 1.0, self.duration);
+            self.easing.apply(t)
+        };
+
+        let Some(mut transition) = ctx.data(|d| d.get_temp::<PaletteTransition>(id)) else {
+            let transition = PaletteTransition {
+                from: target,
+                to: target,
+                generation: 0,
+            };
+            ctx.data_mut(|d| d.insert_temp(id, transition));
+            return target;
+        };
+        if transition.to != target {
+            // Start from what is on screen, in case the last switch is still fading
+            transition = PaletteTransition {
+                from: lerp(&transition.from, &transition.to, progress(&transition)),
+                to: target,
+                generation: transition.generation + 1,
+            };
+            // A new value starts where it's first asked to be
+            let _start: f32 =
+                ctx.animate_value_with_time(id.with(transition.generation), 0.0, self.duration);
+            ctx.data_mut(|d| d.insert_temp(id, transition));
+        }
+        lerp(&transition.from, &transition.to, progress(&transition))
+    }
+}
+
+fn lerp(from: &Palette, to: &Palette, t: f32) -> Palette {
+    let mut palette = *to;
+    for role in Role::ALL {
+        palette.set(role, from.get(role).lerp_to_gamma(to.get(role), t));
+    }
+    palette
+}
@@ -1,4 +1,19 @@
 use egui::FontId;
+use egui_widget_themenator::ThemeVariant;
+
+use crate::action::{ContextAction, TexiEvent, TexiEventKind};
+use crate::animation::AnimationSettings;
+use crate::auto_theme::AutoTheme;
+use crate::custom_theme::{self, CustomTheme};
+use crate::icon_registry::IconRegistry;
+use crate::menu_file::{MenuError, Menus};
+use crate::menu_layout::{MenuLayout, MenuSlot};
+use crate::page_timing::TimingPage;
+use crate::pages::{PageContext, PageId, Router};
+use crate::persistence::{PersistedState, SavedTheme};
+use crate::texi_menu::TexiMenu;
+use crate::toast::Toasts;
+
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg(debug_assertions)]
 const BUILD_MODE: &str = "debug";
@@ -7,46 +22,464 @@ const BUILD_MODE: &str = "release";
 
 #[rustfmt::skip]
 pub struct TexiconDemoApp {
-    top_menu: crate::texi_top_menu::TexiState,
-    side_menu: crate::texi_side_menu::TexiState,
-    central_menu: crate::texi_central_menu::TexiState,
+    top_menu: TexiMenu,
+    side_menu: TexiMenu,
+    central_menu: TexiMenu,
+    // The menus as loaded, which saved layouts refer to
+    loaded_menus: Menus,
+    theme: SavedTheme,
+    auto_theme: AutoTheme,
+    // The registered custom theme and whether it's current, kept for saving
+    custom_theme: Option<(CustomTheme, bool)>,
+    // Kept in the egui context, and copied here for saving
+    animation: AnimationSettings,
+    menu_error: Option<MenuError>,
+    router: Router,
+    last_event: Option<String>,
+    icons: IconRegistry,
+    toasts: Toasts,
 }
 
 impl TexiconDemoApp {
-    pub fn new() -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        Self::from_storage(&cc.egui_ctx, cc.storage)
+    }
+
+    // Create the app without eframe, e.g. to drive it headless with `Context::run_ui`
+    pub fn from_storage(ctx: &egui::Context, storage: Option<&dyn eframe::Storage>) -> Self {
+        let (menus, menu_error) = Menus::load();
+        let (icons, icon_errors) = IconRegistry::load(ctx);
         let mut app = Self {
-            top_menu: crate::texi_top_menu::TexiState::new(),
-            side_menu: crate::texi_side_menu::TexiState::new(),
-            central_menu: crate::texi_central_menu::TexiState::new(),
+            top_menu: crate::texi_top_menu::new_menu(menus.top.clone()),
+            side_menu: crate::texi_side_menu::new_menu(menus.side.clone()),
+            central_menu: crate::texi_central_menu::new_menu(menus.central.clone()),
+            loaded_menus: menus,
+            theme: SavedTheme::from_variant(ThemeVariant::get_current(ctx)),
+            auto_theme: AutoTheme::default(),
+            custom_theme: None,
+            animation: AnimationSettings::get(ctx),
+            menu_error,
+            router: Router::default(),
+            last_event: None,
+            icons,
+            toasts: Toasts::default(),
         };
+        for err in icon_errors {
+            log::warn!("Skipping an icon: {err}");
+            app.toasts.error(ctx, err.to_string());
+        }
         app.top_menu.set_selected_texicon(0);
         app.side_menu.set_selected_texicon(0);
         app.central_menu.set_selected_texicon(0);
+
+        // Restore the previous session (if any).
+        // Out of range indices are ignored, leaving the first texicon selected.
+        if let Some(state) = PersistedState::load(storage) {
+            // The saved order first, which the selected indices refer to
+            match state
+                .menu_layout
+                .map(|layout| layout.items(&app.loaded_menus))
+            {
+                Some(Some(items)) => {
+                    app.top_menu.set_items(items.top);
+                    app.side_menu.set_items(items.side);
+                    app.central_menu.set_items(items.central);
+                }
+                Some(None) => log::info!("Ignoring a saved menu layout that doesn't fit the menus"),
+                None => {}
+            }
+            app.top_menu.set_selected_texicon(state.top_selected);
+            app.side_menu.set_selected_texicon(state.side_selected);
+            app.central_menu
+                .set_selected_texicon(state.central_selected);
+            // The saved order cleared the selection, so an ignored index leaves none
+            for menu in app.menus_mut() {
+                if menu.selected_texicon().is_none() {
+                    menu.set_selected_texicon(0);
+                }
+            }
+            app.theme = state.theme;
+            app.auto_theme = state.auto_theme;
+            app.animation = state.animation;
+            app.animation.set(ctx);
+            ThemeVariant::set_current(ctx, app.theme.to_variant());
+            if let Some(theme) = state.custom_theme {
+                custom_theme::register(ctx, theme);
+                custom_theme::set_active(ctx, state.custom_active);
+            }
+            app.router.restore(state.page);
+        }
+        // A deep link wins over the saved page
+        #[cfg(target_arch = "wasm32")]
+        app.router.follow_url_hash();
+        app.navigate(app.router.current());
         app
     }
+
+    // Start on the timing page, with the stress test showing `count` texicons
+    pub fn with_stress(mut self, count: usize) -> Self {
+        self.router
+            .replace(Box::new(TimingPage::with_stress(count)));
+        self.navigate(PageId::Timing);
+        self
+    }
+
+    // Read-only access to the menus, for tests and tools
+    pub fn top_menu(&self) -> &TexiMenu {
+        &self.top_menu
+    }
+    pub fn side_menu(&self) -> &TexiMenu {
+        &self.side_menu
+    }
+    pub fn central_menu(&self) -> &TexiMenu {
+        &self.central_menu
+    }
+
+    pub fn current_page(&self) -> PageId {
+        self.router.current()
+    }
+    pub fn icons(&self) -> &IconRegistry {
+        &self.icons
+    }
+    pub fn toasts(&self) -> &Toasts {
+        &self.toasts
+    }
+
+    // Show a page, and select its entry in the side menu
+    fn navigate(&mut self, page: PageId) {
+        self.router.navigate(page);
+        if let Some(idx) = self.side_menu.position_of(page.action()) {
+            self.side_menu.set_selected_texicon(idx);
+        }
+    }
+
+    // -------------
+    // Menu events
+    // -------------
+    // The page of the last activated texicon with one
+    fn activated_page(events: &[TexiEvent]) -> Option<PageId> {
+        events
+            .iter()
+            .rev()
+            .filter(|event| event.kind == TexiEventKind::Activated)
+            .find_map(|event| event.action.and_then(PageId::from_action))
+    }
+
+    fn handle_events(&mut self, menu: &str, events: Vec<TexiEvent>) {
+        for event in events {
+            log::debug!("{menu}: {event:?}");
+            self.last_event = Some(format!(
+                "{menu}, texicon {}: {:?} ({})",
+                event.item,
+                event.kind,
+                event
+                    .action
+                    .map_or_else(|| "no action".to_owned(), |action| format!("{action:?}"))
+            ));
+        }
+    }
+
+    fn menu(&self, slot: MenuSlot) -> &TexiMenu {
+        match slot {
+            MenuSlot::Top => &self.top_menu,
+            MenuSlot::Side => &self.side_menu,
+            MenuSlot::Central => &self.central_menu,
+        }
+    }
+    fn menu_mut(&mut self, slot: MenuSlot) -> &mut TexiMenu {
+        match slot {
+            MenuSlot::Top => &mut self.top_menu,
+            MenuSlot::Side => &mut self.side_menu,
+            MenuSlot::Central => &mut self.central_menu,
+        }
+    }
+
+    // Context menu picks, double- and middle-clicks on the texicons of one menu.
+    // A double-click pins the texicon to the top menu, a middle-click opens its page in a new tab.
+    fn run_commands(&mut self, ctx: &egui::Context, slot: MenuSlot, events: &[TexiEvent]) {
+        for event in events {
+            let command = match event.kind {
+                TexiEventKind::ContextChosen(command) => command,
+                TexiEventKind::DoubleClicked => ContextAction::PinToTopMenu,
+                TexiEventKind::MiddleClicked => ContextAction::OpenInNewTab,
+                _ => continue,
+            };
+            let page = event.action.and_then(PageId::from_action);
+            match (command, page) {
+                (ContextAction::Open, Some(page)) => self.navigate(page),
+                (ContextAction::OpenInNewTab, Some(page)) => self.open_in_new_tab(ctx, page),
+                (ContextAction::PinToTopMenu, _) => self.pin(ctx, slot, event.item),
+                (ContextAction::Unpin, _) => {
+                    let menu = self.menu_mut(slot);
+                    let name = menu.name().to_lowercase();
+                    if let Some(texicon) = menu.remove_item(event.item) {
+                        self.toasts
+                            .info(ctx, format!("Took {} off the {name}", texicon.text));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Add a copy of texicon `idx` of `slot` to the top menu, unless it's there already.
+    // Texicons of the top menu itself are left alone.
+    fn pin(&mut self, ctx: &egui::Context, slot: MenuSlot, idx: usize) {
+        if slot == MenuSlot::Top {
+            return;
+        }
+        let Some(texicon) = self.menu(slot).item(idx) else {
+            return;
+        };
+        let pinned = texicon.pinned();
+        let origin = pinned.origin.as_ref();
+        if origin.is_some_and(|origin| self.top_menu.has_origin(origin)) {
+            self.toasts
+                .info(ctx, format!("{} is on the top menu already", pinned.text));
+            return;
+        }
+        self.toasts
+            .info(ctx, format!("Pinned {} to the top menu", pinned.text));
+        self.top_menu.push_item(pinned);
+    }
+
+    // Show `page` in a new browser tab, through its URL hash
+    #[cfg(target_arch = "wasm32")]
+    fn open_in_new_tab(&mut self, ctx: &egui::Context, page: PageId) {
+        ctx.open_url(egui::OpenUrl::new_tab(format!("#{}", page.slug())));
+        self.toasts
+            .info(ctx, format!("Opened #{} in a new tab", page.slug()));
+    }
+
+    // There are no tabs natively, so the page opens here
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_in_new_tab(&mut self, ctx: &egui::Context, page: PageId) {
+        self.navigate(page);
+        self.toasts
+            .info(ctx, "New tabs need the web build, so the page opened here");
+    }
+
+    // -------------
+    // Dropped files
+    // -------------
+    // SVG/PNG files dropped on the window are added to the icon registry
+    fn import_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        for file in &dropped {
+            match self.icons.import(ctx, file) {
+                Ok(name) => self.toasts.info(ctx, format!("Imported the icon {name}")),
+                Err(err) => {
+                    log::warn!("Icon import failed: {err}");
+                    self.toasts.error(ctx, err.to_string());
+                }
+            }
+        }
+    }
+
+    // Dim the window while files are dragged over it
+    fn preview_dropped_files(ctx: &egui::Context) {
+        if ctx.input(|i| i.raw.hovered_files.is_empty()) {
+            return;
+        }
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("file_drop_target"),
+        ));
+        let rect = ctx.content_rect();
+        painter.rect_filled(rect, 0, egui::Color32::from_black_alpha(192));
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "Drop SVG or PNG files to import them as icons",
+            FontId::proportional(24.),
+            egui::Color32::WHITE,
+        );
+    }
+
+    // The menus in keyboard (Tab) order
+    fn menus_mut(&mut self) -> [&mut TexiMenu; 3] {
+        [
+            &mut self.top_menu,
+            &mut self.side_menu,
+            &mut self.central_menu,
+        ]
+    }
+
+    // Tab / Shift+Tab move the keyboard focus from menu to menu, as shown.
+    // Past the last menu (or before the first) it goes on to egui's widgets, e.g. the page's,
+    // and it's left alone while one of them has the egui focus.
+    fn handle_menu_tab(&mut self, ctx: &egui::Context) {
+        use egui::{Key, Modifiers};
+
+        let side_end = self
+            .side_menu
+            .item_id(self.side_menu.item_count().saturating_sub(1));
+        let mut menus = self.menus_mut();
+        let focused = menus.iter().position(|menu| menu.has_keyboard_focus());
+        if focused.is_none() && ctx.memory(|mem| mem.focused().is_some()) {
+            return;
+        }
+        let backwards = ctx.input_mut(|i| {
+            if i.consume_key(Modifiers::SHIFT, Key::Tab) {
+                Some(true)
+            } else if i.consume_key(Modifiers::NONE, Key::Tab) {
+                Some(false)
+            } else {
+                None
+            }
+        });
+        let Some(backwards) = backwards else {
+            return;
+        };
+
+        // Menus that aren't shown (e.g. the central menu on most pages) are skipped
+        let shown: Vec<usize> = (0..menus.len())
+            .filter(|&idx| menus.get(idx).is_some_and(|menu| menu.is_shown(ctx)))
+            .collect();
+        let at = focused.and_then(|idx| shown.iter().position(|&shown_idx| shown_idx == idx));
+        let next = match (at, backwards) {
+            (None, false) => Some(0),
+            (None, true) => shown.len().checked_sub(1),
+            (Some(at), false) => Some(at + 1),
+            (Some(at), true) => at.checked_sub(1),
+        }
+        .and_then(|at| shown.get(at).copied());
+        for (idx, menu) in menus.iter_mut().enumerate() {
+            menu.set_keyboard_focus(Some(idx) == next);
+        }
+
+        // egui has seen the Tab too, and moves its own focus along in this frame. Start it
+        // from the menu's focused texicon, so it stays in the menu, or after the side menu,
+        // so it goes on to the page.
+        let start = match next {
+            Some(next) => menus
+                .get(next)
+                .and_then(|menu| menu.item_id(menu.focused_item().unwrap_or_default())),
+            None if !backwards => side_end,
+            None => None,
+        };
+        ctx.memory_mut(|mem| match start {
+            Some(id) => mem.request_focus(id),
+            None => {
+                if let Some(id) = mem.focused() {
+                    mem.surrender_focus(id);
+                }
+            }
+        });
+    }
+
+    // egui's own focus on a texicon (after a Tab or a click) is the menus' business: the menu
+    // takes it over, unless another one has the keyboard focus already. Anywhere else it takes
+    // the keys from the menus.
+    fn sync_egui_focus(&mut self, ctx: &egui::Context) {
+        let Some(id) = ctx.memory(|mem| mem.focused()) else {
+            return;
+        };
+        let mut menus = self.menus_mut();
+        let Some(owner) = menus
+            .iter()
+            .position(|menu| menu.item_with_id(id).is_some())
+        else {
+            for menu in &mut menus {
+                menu.set_keyboard_focus(false);
+            }
+            return;
+        };
+        if !menus.iter().any(|menu| menu.has_keyboard_focus()) {
+            if let Some(menu) = menus.get_mut(owner) {
+                menu.set_keyboard_focus(true);
+            }
+        }
+        ctx.memory_mut(|mem| mem.surrender_focus(id));
+    }
+
+    // A click moves the keyboard focus to the clicked menu
+    fn sync_menu_focus(&mut self) {
+        let mut menus = self.menus_mut();
+        if let Some(clicked) = menus.iter().position(|menu| menu.took_focus()) {
+            for (idx, menu) in menus.iter_mut().enumerate() {
+                if idx != clicked {
+                    menu.set_keyboard_focus(false);
+                }
+            }
+        }
+    }
+
+    fn persisted_state(&self) -> PersistedState {
+        PersistedState {
+            // Indices into the saved layout, which the selection is restored on
+            top_selected: MenuLayout::selected_index(&self.top_menu),
+            side_selected: MenuLayout::selected_index(&self.side_menu),
+            central_selected: MenuLayout::selected_index(&self.central_menu),
+            theme: self.theme,
+            auto_theme: self.auto_theme,
+            animation: self.animation,
+            menu_layout: Some(MenuLayout::of(
+                &self.top_menu,
+                &self.side_menu,
+                &self.central_menu,
+            )),
+            page: self.router.current(),
+            custom_theme: self.custom_theme.as_ref().map(|(theme, _)| theme.clone()),
+            custom_active: self
+                .custom_theme
+                .as_ref()
+                .is_some_and(|&(_, active)| active),
+            ..PersistedState::new()
+        }
+    }
 }
 
 impl eframe::App for TexiconDemoApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.persisted_state().save(storage);
+    }
+
     fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
+        self.show(ui);
+    }
+}
+
+impl TexiconDemoApp {
+    // Everything the app draws each frame
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        #[cfg(target_arch = "wasm32")]
+        if self.router.follow_url_hash() {
+            self.navigate(self.router.current());
+        }
+        self.sync_egui_focus(ui.ctx());
+        self.handle_menu_tab(ui.ctx());
+        self.import_dropped_files(ui.ctx());
+
         egui::Panel::top("top_panel")
             .exact_size(150.)
             .resizable(false)
             .show(ui, |ui| {
-                self.top_menu.draw_texicons(ui);
+                let events = self.top_menu.draw_texicons(ui);
+                // Pinned texicons lead to their pages
+                if let Some(page) = Self::activated_page(&events) {
+                    self.navigate(page);
+                }
+                self.run_commands(ui.ctx(), MenuSlot::Top, &events);
+                self.handle_events("Top menu", events);
             });
-        let top_menu_benchmark = self.top_menu.get_benchmark();
 
         egui::Panel::left("left_panel")
             .exact_size(150.)
             .resizable(false)
             .show(ui, |ui| {
-                self.side_menu.draw_texicons(ui);
+                ui.add_space(20.);
+                let events = self.side_menu.draw_texicons(ui);
+                // The side menu chooses the page
+                if let Some(page) = Self::activated_page(&events) {
+                    self.navigate(page);
+                }
+                self.run_commands(ui.ctx(), MenuSlot::Side, &events);
+                self.handle_events("Side menu", events);
             });
-        let side_menu_benchmark = self.side_menu.get_benchmark();
 
         egui::CentralPanel::default().show(ui, |ui| {
-            let central_menu_benchmark = self.central_menu.get_benchmark();
-            ui.horizontal(|ui| {
+            ui.horizontal_wrapped(|ui| {
                 // Theme selector
 
                 // let theme_config = egui_widget_themenator::ThemeConfig {
@@ -94,7 +527,28 @@ impl eframe::App for TexiconDemoApp {
                     .add(MACCHIATO)
                     .add(MOCHA);
 
-                ui.add(themenator);
+                // Switching to the custom theme (here or in the theme editor) ends auto mode,
+                // and the OS may have switched between light and dark mode
+                if custom_theme::is_active(ui.ctx()) {
+                    self.auto_theme.enabled = false;
+                }
+                if self.auto_theme.follow(ui.ctx()) {
+                    self.theme = SavedTheme::from_variant(ThemeVariant::get_current(ui.ctx()));
+                }
+                let picked = ui.add(themenator).clicked();
+                let theme = SavedTheme::from_variant(ThemeVariant::get_current(ui.ctx()));
+                // Picking one of the themenator's variants leaves the custom and auto themes
+                if picked || theme != self.theme {
+                    if custom_theme::is_active(ui.ctx()) {
+                        custom_theme::set_active(ui.ctx(), false);
+                    }
+                    self.auto_theme.enabled = false;
+                }
+                self.theme = theme;
+                custom_theme::entry(ui);
+                self.auto_theme.ui(ui);
+                self.custom_theme = custom_theme::registered(ui.ctx())
+                    .map(|theme| (theme, custom_theme::is_active(ui.ctx())));
 
                 // let themenator = egui_widget_themenator::Themenator::new().default_themes_four();
                 // ui.add(themenator);
@@ -123,15 +577,33 @@ impl eframe::App for TexiconDemoApp {
                 );
             });
             ui.add_space(10.);
-            print_text(ui);
-            print_benchmarks(
-                ui,
-                &top_menu_benchmark,
-                &side_menu_benchmark,
-                &central_menu_benchmark,
-            );
-            self.central_menu.draw_texicons(ui);
+            if let Some(err) = &self.menu_error {
+                print_bullets(
+                    ui,
+                    &format!("-- Using the built-in menus. {err}"),
+                    TextStyle::Warning,
+                );
+            }
+            let mut cx = PageContext {
+                top_menu: &mut self.top_menu,
+                side_menu: &mut self.side_menu,
+                central_menu: &mut self.central_menu,
+                loaded_menus: &self.loaded_menus,
+                last_event: self.last_event.as_deref(),
+                icons: &self.icons,
+                events: Vec::new(),
+            };
+            self.router.show(ui, &mut cx);
+            let events = cx.events;
+            self.run_commands(ui.ctx(), MenuSlot::Central, &events);
+            self.handle_events("Central menu", events);
         });
+
+        self.animation = AnimationSettings::get(ui.ctx());
+        self.sync_menu_focus();
+        self.sync_egui_focus(ui.ctx());
+        Self::preview_dropped_files(ui.ctx());
+        self.toasts.show(ui.ctx());
     }
 }
 
@@ -152,7 +624,7 @@ enum TextStyle {
     Warning,
 }
 
-fn print_text(ui: &mut egui::Ui) {
+pub(crate) fn print_intro(ui: &mut egui::Ui) {
     print_heading(
         ui,
         &format!(
@@ -166,19 +638,15 @@ fn print_text(ui: &mut egui::Ui) {
     }
 
     print_bullets(ui, RED_BULLETS[0], TextStyle::Warning);
+}
 
-    // Benchmarks
-    print_heading(ui, "Timing benchmarks (for each group, non-wasm)");
-
-    // ---------------
-    // Print the stats
-    // ---------------
+pub(crate) fn print_build_info(ui: &mut egui::Ui) {
     let s = get_os_info();
     let s = format!("{} compiled in {} mode.", s, BUILD_MODE);
     print_bullets(ui, &s, TextStyle::Normal);
 }
 
-fn print_heading(ui: &mut egui::Ui, s: &str) {
+pub(crate) fn print_heading(ui: &mut egui::Ui, s: &str) {
     ui.add_space(10.);
     ui.add(egui::Label::new(
         egui::RichText::new(s)
@@ -201,44 +669,6 @@ fn print_bullets(ui: &mut egui::Ui, s: &str, text_style: TextStyle) {
     ui.add_space(4.);
 }
 
-fn print_benchmarks(
-    ui: &mut egui::Ui,
-    tm_bm: &crate::texi_top_menu::Benchmark,
-    sm_bm: &crate::texi_side_menu::Benchmark,
-    cm_bm: &crate::texi_central_menu::Benchmark,
-) {
-    let tm = format!(
-        "> Top Menu       Count: {},  Sum: {} us,  Average: {:2.1} us",
-        tm_bm.count, tm_bm.sum, tm_bm.average
-    );
-    let sm = format!(
-        "> Side Menu      Count: {},  Sum: {} us,  Average: {:2.1} us",
-        sm_bm.count, sm_bm.sum, sm_bm.average
-    );
-    let cm = format!(
-        "> Central Menu   Count: {},  Sum: {} us,  Average: {:2.1} us",
-        cm_bm.count, cm_bm.sum, cm_bm.average
-    );
-    ui.add(egui::Label::new(
-        egui::RichText::new(tm)
-            .color(ui.visuals().strong_text_color())
-            .font(FontId::new(16., egui::FontFamily::Monospace)),
-    ));
-    ui.add_space(4.);
-    ui.add(egui::Label::new(
-        egui::RichText::new(sm)
-            .color(ui.visuals().strong_text_color())
-            .font(FontId::new(16., egui::FontFamily::Monospace)),
-    ));
-    ui.add_space(4.);
-    ui.add(egui::Label::new(
-        egui::RichText::new(cm)
-            .color(ui.visuals().strong_text_color())
-            .font(FontId::new(16., egui::FontFamily::Monospace)),
-    ));
-    ui.add_space(4.);
-}
-
 pub fn get_os_info() -> String {
     let info = os_info::get();
     format!(
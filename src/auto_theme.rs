@@ -0,0 +1,142 @@
+use egui::Theme;
+use egui_widget_themenator::ThemeVariant;
+
+use crate::custom_theme;
+use crate::persistence::SavedTheme;
+
+// The variants the OS's dark mode may map to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum DarkVariant {
+    Frappe,
+    Macchiato,
+    #[default]
+    Mocha,
+}
+
+impl DarkVariant {
+    pub const ALL: [Self; 3] = [Self::Frappe, Self::Macchiato, Self::Mocha];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Frappe => "Frappe",
+            Self::Macchiato => "Macchiato",
+            Self::Mocha => "Mocha",
+        }
+    }
+
+    fn variant(self) -> ThemeVariant {
+        match self {
+            Self::Frappe => ThemeVariant::Frappe,
+            Self::Macchiato => ThemeVariant::Macchiato,
+            Self::Mocha => ThemeVariant::Mocha,
+        }
+    }
+}
+
+// ----------
+// Auto theme
+// ----------
+// Follow the OS light/dark preference: light is Latte, dark is the chosen variant.
+// Picking a theme by hand turns it off.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct AutoTheme {
+    pub enabled: bool,
+    pub dark: DarkVariant,
+}
+
+impl AutoTheme {
+    pub fn variant(self, system: Theme) -> ThemeVariant {
+        match system {
+            Theme::Light => ThemeVariant::Latte,
+            Theme::Dark => self.dark.variant(),
+        }
+    }
+
+    // Switch to the variant for the OS preference, if that isn't the current theme.
+    // Returns true if it switched.
+    pub fn follow(self, ctx: &egui::Context) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        #[cfg(target_arch = "wasm32")]
+        listen_for_changes(ctx);
+
+        let Some(system) = system_theme(ctx) else {
+            return false;
+        };
+        let wanted = self.variant(system);
+        let current = SavedTheme::from_variant(ThemeVariant::get_current(ctx));
+        if SavedTheme::from_variant(wanted) == current {
+            return false;
+        }
+        ThemeVariant::set_current(ctx, wanted);
+        true
+    }
+
+    // An "Auto" toggle and the variant used in dark mode
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let toggled = ui
+            .checkbox(&mut self.enabled, "Auto")
+            .on_hover_text("Follow the system's light or dark mode")
+            .changed();
+        if toggled && self.enabled {
+            custom_theme::set_active(ui.ctx(), false);
+        }
+        ui.add_enabled_ui(self.enabled, |ui| {
+            egui::ComboBox::from_id_salt("auto_theme_dark")
+                .selected_text(format!("Dark: {}", self.dark.name()))
+                .show_ui(ui, |ui| {
+                    for dark in DarkVariant::ALL {
+                        ui.selectable_value(&mut self.dark, dark, dark.name());
+                    }
+                });
+        });
+    }
+}
+
+// The OS preference: egui's system theme natively, the media query on the web
+#[cfg(not(target_arch = "wasm32"))]
+fn system_theme(ctx: &egui::Context) -> Option<Theme> {
+    ctx.system_theme()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn system_theme(_ctx: &egui::Context) -> Option<Theme> {
+    Some(if dark_query()?.matches() {
+        Theme::Dark
+    } else {
+        Theme::Light
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn dark_query() -> Option<web_sys::MediaQueryList> {
+    web_sys::window()?
+        .match_media("(prefers-color-scheme: dark)")
+        .ok()?
+}
+
+// Repaint when the OS switches, so an idle app still follows it. Registered once per context.
+// (Natively eframe repaints when the system theme changes.)
+#[cfg(target_arch = "wasm32")]
+fn listen_for_changes(ctx: &egui::Context) {
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast as _;
+
+    let id = egui::Id::new("auto_theme_listener");
+    if ctx.data(|d| d.get_temp::<bool>(id)).unwrap_or(false) {
+        return;
+    }
+    let Some(query) = dark_query() else {
+        return;
+    };
+    let repaint = ctx.clone();
+    let on_change = Closure::<dyn FnMut()>::new(move || repaint.request_repaint());
+    let added =
+        query.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref());
+    if added.is_ok() {
+        // The listener lives as long as the page
+        on_change.forget();
+        ctx.data_mut(|d| d.insert_temp(id, true));
+    }
+}
@@ -0,0 +1,166 @@
+use egui::{vec2, FontId, Rect};
+
+use crate::palette::{Palette, Role};
+
+// === Constants ===
+#[rustfmt::skip] const BADGE_HEIGHT: f32    = 18.0;
+#[rustfmt::skip] const BADGE_PADDING: f32   = 5.0;
+#[rustfmt::skip] const BADGE_TEXT_SIZE: f32 = 11.0;
+#[rustfmt::skip] const DOT_RADIUS: f32      = 5.0;
+// A ring in the background colour sets the badge apart from the image
+#[rustfmt::skip] const RING_WIDTH: f32      = 1.5;
+#[rustfmt::skip] const DEFAULT_MAX: u32     = 99;
+
+// What a badge shows
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum BadgeContent {
+    // A counter, hidden at zero
+    Count(u32),
+    // A status dot
+    Dot,
+    // A short label, e.g. "new"
+    Text(String),
+}
+
+// Which corner of the texicon's image the badge sits on
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum BadgeCorner {
+    #[default]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+impl BadgeCorner {
+    pub const ALL: [Self; 4] = [
+        Self::TopRight,
+        Self::TopLeft,
+        Self::BottomRight,
+        Self::BottomLeft,
+    ];
+
+    fn point(self, rect: Rect) -> egui::Pos2 {
+        match self {
+            Self::TopRight => rect.right_top(),
+            Self::TopLeft => rect.left_top(),
+            Self::BottomRight => rect.right_bottom(),
+            Self::BottomLeft => rect.left_bottom(),
+        }
+    }
+}
+
+// -----
+// Badge
+// -----
+// A counter, dot or label drawn over a corner of a texicon's image.
+// In menu files: `badge: (content: Count(3))`, optionally with
+// `corner: TopLeft`, `color: Blue` (a palette role) and `max_count: 9`.
+#[rustfmt::skip]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Badge {
+    pub content:   BadgeContent,
+    #[serde(default)]
+    pub corner:    BadgeCorner,
+    #[serde(default = "default_color")]
+    pub color:     Role,
+    // Bigger counts show as e.g. "99+"
+    #[serde(default = "default_max")]
+    pub max_count: u32,
+}
+
+fn default_color() -> Role {
+    Role::Red
+}
+
+fn default_max() -> u32 {
+    DEFAULT_MAX
+}
+
+impl Badge {
+    pub fn new(content: BadgeContent) -> Self {
+        Self {
+            content,
+            corner: BadgeCorner::default(),
+            color: default_color(),
+            max_count: DEFAULT_MAX,
+        }
+    }
+
+    pub fn count(count: u32) -> Self {
+        Self::new(BadgeContent::Count(count))
+    }
+
+    pub fn dot() -> Self {
+        Self::new(BadgeContent::Dot)
+    }
+
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::new(BadgeContent::Text(text.into()))
+    }
+
+    pub fn corner(mut self, corner: BadgeCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    pub fn color(mut self, color: Role) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn max_count(mut self, max_count: u32) -> Self {
+        self.max_count = max_count;
+        self
+    }
+
+    // A zero count or empty text shows nothing
+    pub fn is_visible(&self) -> bool {
+        match &self.content {
+            BadgeContent::Count(count) => *count > 0,
+            BadgeContent::Dot => true,
+            BadgeContent::Text(text) => !text.is_empty(),
+        }
+    }
+
+    // The text on the badge; a dot has none
+    pub fn label(&self) -> Option<String> {
+        match &self.content {
+            BadgeContent::Count(count) if *count > self.max_count => {
+                Some(format!("{}+", self.max_count))
+            }
+            BadgeContent::Count(count) => Some(count.to_string()),
+            BadgeContent::Dot => None,
+            BadgeContent::Text(text) => Some(text.clone()),
+        }
+    }
+
+    // Draw the badge centred on its corner of `img_rect`
+    pub fn paint(&self, painter: &egui::Painter, img_rect: Rect, palette: &Palette) {
+        if !self.is_visible() {
+            return;
+        }
+        let fill = palette.get(self.color);
+        let ring = egui::Stroke::new(RING_WIDTH, palette.base);
+        let center = self.corner.point(img_rect);
+        let Some(label) = self.label() else {
+            painter.circle(center, DOT_RADIUS, fill, ring);
+            return;
+        };
+
+        let galley =
+            painter.layout_no_wrap(label, FontId::proportional(BADGE_TEXT_SIZE), palette.base);
+        // A circle for one digit, a pill for more
+        let width = (galley.size().x + 2.0 * BADGE_PADDING).max(BADGE_HEIGHT);
+        let rect = Rect::from_center_size(center, vec2(width, BADGE_HEIGHT));
+        painter.rect(
+            rect,
+            BADGE_HEIGHT / 2.0,
+            fill,
+            ring,
+            egui::StrokeKind::Outside,
+        );
+        painter.galley(rect.center() - galley.size() / 2.0, galley, palette.base);
+    }
+}
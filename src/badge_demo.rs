@@ -0,0 +1,150 @@
+use crate::action::Action;
+use crate::badge::{Badge, BadgeContent, BadgeCorner};
+use crate::palette::Role;
+use crate::texi_menu::TexiMenu;
+
+// === Constants ===
+#[rustfmt::skip] const MIN_INTERVAL: f32     = 0.1;
+#[rustfmt::skip] const MAX_INTERVAL: f32     = 10.0;
+#[rustfmt::skip] const DEFAULT_INTERVAL: f32 = 1.0;
+#[rustfmt::skip] const MAX_MAX_COUNT: u32    = 999;
+// A quick way past the "99+" cap
+#[rustfmt::skip] const JUMP: u32             = 50;
+
+// What the demo badge shows
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Count,
+    Dot,
+    Text,
+}
+
+// ----------
+// Badge demo
+// ----------
+// A badge on the side menu's "Timing Stuff" texicon, counting up on a timer
+pub struct BadgeDemo {
+    pub enabled: bool,
+    kind: Kind,
+    text: String,
+    corner: BadgeCorner,
+    color: Role,
+    max_count: u32,
+    // Seconds between counts
+    interval: f32,
+    count: u32,
+    // `InputState::time` of the last count
+    last_tick: Option<f64>,
+}
+
+impl Default for BadgeDemo {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            kind: Kind::Count,
+            text: "new".to_owned(),
+            corner: BadgeCorner::default(),
+            color: Role::Red,
+            max_count: 99,
+            interval: DEFAULT_INTERVAL,
+            count: 0,
+            last_tick: None,
+        }
+    }
+}
+
+impl BadgeDemo {
+    pub fn badge(&self) -> Option<Badge> {
+        if !self.enabled {
+            return None;
+        }
+        let content = match self.kind {
+            Kind::Count => BadgeContent::Count(self.count),
+            Kind::Dot => BadgeContent::Dot,
+            Kind::Text => BadgeContent::Text(self.text.clone()),
+        };
+        Some(
+            Badge::new(content)
+                .corner(self.corner)
+                .color(self.color)
+                .max_count(self.max_count),
+        )
+    }
+
+    // Count up once per interval, and wake up for the next count
+    pub fn tick(&mut self, ctx: &egui::Context) {
+        if !self.enabled {
+            self.last_tick = None;
+            return;
+        }
+        let now = ctx.input(|i| i.time);
+        let interval = f64::from(self.interval);
+        let last = *self.last_tick.get_or_insert(now);
+        let ticks = ((now - last) / interval).floor();
+        if ticks >= 1.0 {
+            self.count = self.count.saturating_add(ticks as u32);
+            self.last_tick = Some(last + ticks * interval);
+        }
+        let next = self.last_tick.unwrap_or(now) + interval - now;
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(next.max(0.0)));
+    }
+
+    // Put the badge on the "Timing Stuff" texicon of `menu`
+    pub fn apply(&self, menu: &mut TexiMenu) {
+        if let Some(idx) = menu.position_of(Action::Timing) {
+            menu.set_badge(idx, self.badge());
+        }
+    }
+
+    pub fn controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.enabled, "Badge on \"Timing Stuff\"");
+            ui.add_enabled_ui(self.enabled, |ui| {
+                ui.radio_value(&mut self.kind, Kind::Count, "Count");
+                ui.radio_value(&mut self.kind, Kind::Dot, "Dot");
+                ui.radio_value(&mut self.kind, Kind::Text, "Text");
+                match self.kind {
+                    Kind::Count => self.count_controls(ui),
+                    Kind::Dot => {}
+                    Kind::Text => {
+                        ui.add(egui::TextEdit::singleline(&mut self.text).desired_width(60.0));
+                    }
+                }
+                egui::ComboBox::from_id_salt("badge_corner")
+                    .selected_text(format!("{:?}", self.corner))
+                    .show_ui(ui, |ui| {
+                        for corner in BadgeCorner::ALL {
+                            ui.selectable_value(&mut self.corner, corner, format!("{corner:?}"));
+                        }
+                    });
+                egui::ComboBox::from_id_salt("badge_color")
+                    .selected_text(self.color.name())
+                    .show_ui(ui, |ui| {
+                        for role in Role::ALL {
+                            ui.selectable_value(&mut self.color, role, role.name());
+                        }
+                    });
+            });
+        });
+    }
+
+    fn count_controls(&mut self, ui: &mut egui::Ui) {
+        ui.label(format!("{} so far", self.count));
+        ui.add(
+            egui::Slider::new(&mut self.interval, MIN_INTERVAL..=MAX_INTERVAL)
+                .logarithmic(true)
+                .text("s per count"),
+        );
+        ui.add(
+            egui::DragValue::new(&mut self.max_count)
+                .range(1..=MAX_MAX_COUNT)
+                .prefix("max "),
+        );
+        if ui.button(format!("+{JUMP}")).clicked() {
+            self.count = self.count.saturating_add(JUMP);
+        }
+        if ui.button("Mark as read").clicked() {
+            self.count = 0;
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+
+// === Constants ===
+// How many frames the rolling window keeps
+#[rustfmt::skip] pub const WINDOW_FRAMES: usize = 1000;
+
+// ---------
+// Stopwatch
+// ---------
+// Microsecond timer that works natively and on wasm (via `performance.now()`)
+pub struct Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: std::time::Instant,
+    #[cfg(target_arch = "wasm32")]
+    start_ms: f64,
+}
+
+impl Stopwatch {
+    pub fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            start: std::time::Instant::now(),
+            #[cfg(target_arch = "wasm32")]
+            start_ms: performance_now_ms(),
+        }
+    }
+
+    pub fn elapsed_us(&self) -> f64 {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.start.elapsed().as_secs_f64() * 1e6
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            (performance_now_ms() - self.start_ms) * 1e3
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn performance_now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or(0.0, |performance| performance.now())
+}
+
+// -----------
+// Frame times
+// -----------
+// Per-frame draw durations of one menu: a rolling window plus running totals
+#[derive(Clone, Debug)]
+pub struct FrameTimes {
+    window: VecDeque<f64>,
+    capacity: usize,
+    total_count: u64,
+    total_us: f64,
+}
+
+impl Default for FrameTimes {
+    fn default() -> Self {
+        Self::new(WINDOW_FRAMES)
+    }
+}
+
+impl FrameTimes {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            window: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            total_count: 0,
+            total_us: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, micros: f64) {
+        if self.window.len() == self.capacity {
+            self.window.pop_front();
+        }
+        self.window.push_back(micros);
+        self.total_count += 1;
+        self.total_us += micros;
+    }
+
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.total_count = 0;
+        self.total_us = 0.0;
+    }
+
+    // The rolling window, oldest first
+    pub fn samples(&self) -> impl ExactSizeIterator<Item = f64> + '_ {
+        self.window.iter().copied()
+    }
+
+    pub fn latest(&self) -> Option<f64> {
+        self.window.back().copied()
+    }
+
+    pub fn total_count(&self) -> u64 {
+        self.total_count
+    }
+
+    // Mean over every frame since the start (or the last clear)
+    pub fn total_mean(&self) -> f64 {
+        if self.total_count == 0 {
+            0.0
+        } else {
+            self.total_us / self.total_count as f64
+        }
+    }
+
+    // Statistics over the rolling window
+    pub fn stats(&self) -> Option<Stats> {
+        let mut sorted: Vec<f64> = self.samples().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        let percentile = |p: f64| {
+            // Nearest rank
+            let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+            sorted
+                .get(rank.saturating_sub(1))
+                .copied()
+                .unwrap_or_default()
+        };
+        Some(Stats {
+            frames: sorted.len(),
+            min: sorted.first().copied().unwrap_or_default(),
+            max: sorted.last().copied().unwrap_or_default(),
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p50: percentile(50.0),
+            p95: percentile(95.0),
+            p99: percentile(99.0),
+        })
+    }
+}
+
+// Rolling window statistics, in microseconds
+#[rustfmt::skip]
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize)]
+pub struct Stats {
+    pub frames: usize,
+    pub min:    f64,
+    pub max:    f64,
+    pub mean:   f64,
+    pub p50:    f64,
+    pub p95:    f64,
+    pub p99:    f64,
+}
+
+// ------
+// Export
+// ------
+// One row per frame in the rolling window: `menu,frame,micros`
+pub fn to_csv(menus: &[(&str, &FrameTimes)]) -> String {
+    let mut csv = String::from("menu,frame,micros\n");
+    for (name, times) in menus {
+        for (frame, micros) in times.samples().enumerate() {
+            csv.push_str(&format!("{name},{frame},{micros:.1}\n"));
+        }
+    }
+    csv
+}
+
+#[derive(serde::Serialize)]
+struct MenuExport<'a> {
+    menu: &'a str,
+    total_frames: u64,
+    total_mean: f64,
+    window: Option<Stats>,
+    samples: Vec<f64>,
+}
+
+// Statistics and the rolling window of every menu
+pub fn to_json(menus: &[(&str, &FrameTimes)]) -> String {
+    let export: Vec<MenuExport<'_>> = menus
+        .iter()
+        .map(|(name, times)| MenuExport {
+            menu: name,
+            total_frames: times.total_count(),
+            total_mean: times.total_mean(),
+            window: times.stats(),
+            samples: times.samples().collect(),
+        })
+        .collect();
+    serde_json::to_string_pretty(&export).unwrap_or_default()
+}
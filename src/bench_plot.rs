@@ -0,0 +1,216 @@
+use egui::{pos2, vec2, Color32, FontId, Rect, Shape, Stroke};
+
+use crate::bench::{FrameTimes, WINDOW_FRAMES};
+use crate::palette::{Palette, Role};
+
+// === Constants ===
+#[rustfmt::skip] const PLOT_HEIGHT: f32      = 140.0;
+#[rustfmt::skip] const HISTOGRAM_HEIGHT: f32 = 90.0;
+#[rustfmt::skip] const HISTOGRAM_BINS: usize = 40;
+#[rustfmt::skip] const GRID_LINES: usize     = 4;
+#[rustfmt::skip] const LINE_WIDTH: f32       = 1.5;
+#[rustfmt::skip] const DEFAULT_FRAMES: usize = 300;
+#[rustfmt::skip] const MIN_FRAMES: usize     = 50;
+
+// One colour per series, in the order they are passed in
+const SERIES_ROLES: [Role; 3] = [Role::Blue, Role::Green, Role::Mauve];
+
+// -----------------
+// Frame-time plots
+// -----------------
+// A line chart of the last N frame times of each menu, and a histogram of them.
+// Every frame is drawn (no averaging), so one-off spikes stay visible.
+pub struct BenchPlot {
+    frames: usize,
+    paused: bool,
+    // What is shown while paused
+    frozen: Vec<(String, Vec<f64>)>,
+}
+
+impl Default for BenchPlot {
+    fn default() -> Self {
+        Self {
+            frames: DEFAULT_FRAMES,
+            paused: false,
+            frozen: Vec::new(),
+        }
+    }
+}
+
+impl BenchPlot {
+    // Draws the collapsible panel. Returns true if the user asked to reset the timings.
+    pub fn show(&mut self, ui: &mut egui::Ui, series: &[(&str, &FrameTimes)]) -> bool {
+        let mut reset = false;
+        egui::CollapsingHeader::new("Frame time plots")
+            .id_salt("bench_plot")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let pause_text = if self.paused { "Resume" } else { "Pause" };
+                    if ui.button(pause_text).clicked() {
+                        self.paused = !self.paused;
+                    }
+                    if ui.button("Reset").clicked() {
+                        reset = true;
+                        self.frozen.clear();
+                    }
+                    ui.add(
+                        egui::Slider::new(&mut self.frames, MIN_FRAMES..=WINDOW_FRAMES)
+                            .text("frames"),
+                    );
+                });
+
+                if !self.paused {
+                    self.frozen = series
+                        .iter()
+                        .map(|(name, times)| {
+                            let skip = times.samples().len().saturating_sub(self.frames);
+                            ((*name).to_owned(), times.samples().skip(skip).collect())
+                        })
+                        .collect();
+                    // Keep the plot live while it is open
+                    ui.ctx().request_repaint();
+                }
+
+                let palette = Palette::current(ui.ctx());
+                self.draw_legend(ui, &palette);
+                self.draw_lines(ui, &palette);
+                ui.add_space(6.);
+                self.draw_histogram(ui, &palette);
+            });
+        reset
+    }
+
+    fn series_color(palette: &Palette, idx: usize) -> Color32 {
+        let role = SERIES_ROLES.get(idx % SERIES_ROLES.len());
+        palette.get(role.copied().unwrap_or(Role::ACCENT))
+    }
+
+    // The largest sample shown, so the y axis always fits the biggest spike
+    fn max_sample(&self) -> f64 {
+        self.frozen
+            .iter()
+            .flat_map(|(_, samples)| samples.iter().copied())
+            .fold(0.0, f64::max)
+    }
+
+    fn draw_legend(&self, ui: &mut egui::Ui, palette: &Palette) {
+        ui.horizontal(|ui| {
+            for (idx, (name, samples)) in self.frozen.iter().enumerate() {
+                let latest = samples.last().copied().unwrap_or_default();
+                ui.colored_label(
+                    Self::series_color(palette, idx),
+                    format!("\u{25A0} {name}: {latest:.1} us"),
+                );
+            }
+        });
+    }
+
+    fn draw_lines(&self, ui: &mut egui::Ui, palette: &Palette) {
+        let (rect, _resp) = ui.allocate_exact_size(
+            vec2(ui.available_width(), PLOT_HEIGHT),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 4, ui.visuals().extreme_bg_color);
+
+        let y_max = (self.max_sample() * 1.1).max(1.0);
+        draw_grid(&painter, rect, y_max, palette);
+
+        let x_step = rect.width() / (self.frames.max(2) - 1) as f32;
+        for (idx, (_, samples)) in self.frozen.iter().enumerate() {
+            // Right aligned, so the newest frame is always at the right edge
+            let first_x = rect.right() - x_step * samples.len().saturating_sub(1) as f32;
+            let points: Vec<egui::Pos2> = samples
+                .iter()
+                .enumerate()
+                .map(|(i, &micros)| {
+                    let x = first_x + x_step * i as f32;
+                    let y = rect.bottom() - (micros / y_max) as f32 * rect.height();
+                    pos2(x, y)
+                })
+                .collect();
+            painter.add(Shape::line(
+                points,
+                Stroke::new(LINE_WIDTH, Self::series_color(palette, idx)),
+            ));
+        }
+    }
+
+    fn draw_histogram(&self, ui: &mut egui::Ui, palette: &Palette) {
+        let (rect, _resp) = ui.allocate_exact_size(
+            vec2(ui.available_width(), HISTOGRAM_HEIGHT),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 4, ui.visuals().extreme_bg_color);
+
+        let x_max = (self.max_sample() * 1.1).max(1.0);
+        let bins: Vec<[usize; HISTOGRAM_BINS]> = self
+            .frozen
+            .iter()
+            .map(|(_, samples)| {
+                let mut bins = [0; HISTOGRAM_BINS];
+                for &micros in samples {
+                    let bin = ((micros / x_max) * HISTOGRAM_BINS as f64) as usize;
+                    if let Some(count) = bins.get_mut(bin.min(HISTOGRAM_BINS - 1)) {
+                        *count += 1;
+                    }
+                }
+                bins
+            })
+            .collect();
+        let count_max = bins.iter().flatten().copied().max().unwrap_or(0).max(1);
+
+        // Series side by side within each bin
+        let bin_width = rect.width() / HISTOGRAM_BINS as f32;
+        let bar_width = bin_width / bins.len().max(1) as f32;
+        for (idx, series_bins) in bins.iter().enumerate() {
+            let color = Self::series_color(palette, idx).gamma_multiply(0.8);
+            for (bin, &count) in series_bins.iter().enumerate().filter(|&(_, &n)| n > 0) {
+                let height = count as f32 / count_max as f32 * rect.height();
+                let left = rect.left() + bin as f32 * bin_width + idx as f32 * bar_width;
+                let bar = Rect::from_min_max(
+                    pos2(left, rect.bottom() - height),
+                    pos2(left + bar_width, rect.bottom()),
+                );
+                painter.rect_filled(bar, 0, color);
+            }
+        }
+
+        // x axis labels
+        let font = FontId::monospace(11.);
+        let text_col = palette.subtext0;
+        painter.text(
+            rect.left_bottom() + vec2(4., -2.),
+            egui::Align2::LEFT_BOTTOM,
+            "0 us",
+            font.clone(),
+            text_col,
+        );
+        painter.text(
+            rect.right_bottom() + vec2(-4., -2.),
+            egui::Align2::RIGHT_BOTTOM,
+            format!("{x_max:.0} us"),
+            font,
+            text_col,
+        );
+    }
+}
+
+// Horizontal grid lines, labelled in microseconds
+fn draw_grid(painter: &egui::Painter, rect: Rect, y_max: f64, palette: &Palette) {
+    let stroke = Stroke::new(1.0, palette.surface0);
+    let font = FontId::monospace(11.);
+    for line in 1..=GRID_LINES {
+        let fraction = line as f32 / GRID_LINES as f32;
+        let y = rect.bottom() - fraction * rect.height();
+        painter.hline(rect.x_range(), y, stroke);
+        painter.text(
+            pos2(rect.left() + 4., y),
+            egui::Align2::LEFT_TOP,
+            format!("{:.0} us", y_max * f64::from(fraction)),
+            font.clone(),
+            palette.subtext0,
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use egui::Color32;
+use egui_widget_themenator::ThemeVariant;
+
+use crate::palette::{Palette, Role};
+
+// === Constants ===
+#[rustfmt::skip] pub const FILE_NAME: &str = "custom_theme.ron";
+#[rustfmt::skip] const ICON: &str          = "\u{1F3A8}";
+
+// ------------
+// Custom theme
+// ------------
+// A palette made in the theme editor. The themenator only knows its four variants,
+// so the custom theme is one more entry drawn next to it (see `entry`).
+// While it is active `Palette::current` returns it, so every menu follows it.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(into = "ThemeFile", try_from = "ThemeFile")]
+pub struct CustomTheme {
+    pub name: String,
+    // Built on egui's dark visuals, else the light ones
+    pub dark: bool,
+    pub palette: Palette,
+}
+
+impl CustomTheme {
+    pub fn new(name: impl Into<String>, dark: bool, palette: Palette) -> Self {
+        Self {
+            name: name.into(),
+            dark,
+            palette,
+        }
+    }
+
+    // The egui visuals for the palette, so widgets match the menus
+    pub fn visuals(&self) -> egui::Visuals {
+        let p = &self.palette;
+        let mut visuals = if self.dark {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        visuals.override_text_color = Some(p.text);
+        visuals.hyperlink_color = p.blue;
+        visuals.warn_fg_color = p.yellow;
+        visuals.error_fg_color = p.red;
+        visuals.panel_fill = p.base;
+        visuals.window_fill = p.mantle;
+        visuals.window_stroke.color = p.overlay0;
+        visuals.faint_bg_color = p.surface0;
+        visuals.extreme_bg_color = p.crust;
+        visuals.code_bg_color = p.mantle;
+        visuals.selection.bg_fill = p.mauve.gamma_multiply(0.5);
+        visuals.selection.stroke.color = p.mauve;
+        let widgets = &mut visuals.widgets;
+        for (widget, fill, stroke) in [
+            (&mut widgets.noninteractive, p.base, p.subtext0),
+            (&mut widgets.inactive, p.surface0, p.text),
+            (&mut widgets.hovered, p.surface2, p.text),
+            (&mut widgets.active, p.overlay0, p.text),
+            (&mut widgets.open, p.surface0, p.text),
+        ] {
+            widget.bg_fill = fill;
+            widget.weak_bg_fill = fill;
+            widget.fg_stroke.color = stroke;
+        }
+        widgets.noninteractive.bg_stroke.color = p.surface0;
+        visuals
+    }
+
+    pub fn to_ron(&self) -> String {
+        let file = ThemeFile::from(self.clone());
+        ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default()).unwrap_or_default()
+    }
+
+    // Read a theme written by `to_ron`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        ron::from_str(text).map_err(|err| format!("Not a valid theme file: {err}"))
+    }
+}
+
+// === Theme files ===
+// Colours are "#rrggbb" strings keyed by role, e.g. `Mauve: "#cba6f7"`,
+// so the file is easy to edit by hand
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    name: String,
+    dark: bool,
+    colors: BTreeMap<Role, String>,
+}
+
+impl From<CustomTheme> for ThemeFile {
+    fn from(theme: CustomTheme) -> Self {
+        let colors = Role::ALL
+            .into_iter()
+            .map(|role| {
+                let [r, g, b, _] = theme.palette.get(role).to_array();
+                (role, format!("#{r:02x}{g:02x}{b:02x}"))
+            })
+            .collect();
+        Self {
+            name: theme.name,
+            dark: theme.dark,
+            colors,
+        }
+    }
+}
+
+impl TryFrom<ThemeFile> for CustomTheme {
+    type Error = String;
+
+    fn try_from(file: ThemeFile) -> Result<Self, String> {
+        let color = |role: Role| -> Result<Color32, String> {
+            let hex = file
+                .colors
+                .get(&role)
+                .ok_or_else(|| format!("the theme has no {} colour", role.name()))?;
+            Color32::from_hex(hex)
+                .map_err(|err| format!("{}: bad colour {hex:?}: {err:?}", role.name()))
+        };
+        let palette = Palette {
+            base: color(Role::Base)?,
+            mantle: color(Role::Mantle)?,
+            crust: color(Role::Crust)?,
+            surface0: color(Role::Surface0)?,
+            surface2: color(Role::Surface2)?,
+            overlay0: color(Role::Overlay0)?,
+            text: color(Role::Text)?,
+            subtext0: color(Role::Subtext0)?,
+            mauve: color(Role::Mauve)?,
+            teal: color(Role::Teal)?,
+            green: color(Role::Green)?,
+            red: color(Role::Red)?,
+            yellow: color(Role::Yellow)?,
+            blue: color(Role::Blue)?,
+        };
+        Ok(Self::new(file.name, file.dark, palette))
+    }
+}
+
+// === Registration ===
+// Kept in the egui context, like the themenator keeps the current variant
+fn registered_id() -> egui::Id {
+    egui::Id::new("custom_theme")
+}
+
+fn active_id() -> egui::Id {
+    egui::Id::new("custom_theme_active")
+}
+
+// Add the theme as an extra theme entry, or update it. Updating the active theme
+// restyles the app right away, which is how the theme editor previews live.
+pub fn register(ctx: &egui::Context, theme: CustomTheme) {
+    let active = is_active(ctx);
+    ctx.data_mut(|d| d.insert_temp(registered_id(), theme));
+    if active {
+        set_active(ctx, true);
+    }
+}
+
+pub fn registered(ctx: &egui::Context) -> Option<CustomTheme> {
+    ctx.data(|d| d.get_temp(registered_id()))
+}
+
+pub fn is_active(ctx: &egui::Context) -> bool {
+    active_palette(ctx).is_some()
+}
+
+// The palette of the active custom theme, if it is the current theme
+pub fn active_palette(ctx: &egui::Context) -> Option<Palette> {
+    ctx.data(|d| d.get_temp(active_id()))
+}
+
+// Switch to the registered theme, or back to the themenator's variant
+pub fn set_active(ctx: &egui::Context, active: bool) {
+    match registered(ctx).filter(|_| active) {
+        Some(theme) => {
+            ctx.data_mut(|d| d.insert_temp(active_id(), theme.palette));
+            ctx.set_visuals(theme.visuals());
+        }
+        None => {
+            ctx.data_mut(|d| d.remove::<Palette>(active_id()));
+            ThemeVariant::set_current(ctx, ThemeVariant::get_current(ctx));
+        }
+    }
+}
+
+// The custom theme's entry beside the themenator: a toggle showing its name
+pub fn entry(ui: &mut egui::Ui) {
+    let Some(theme) = registered(ui.ctx()) else {
+        return;
+    };
+    let active = is_active(ui.ctx());
+    let resp = ui
+        .selectable_label(active, format!("{ICON} {}", theme.name))
+        .on_hover_text("A custom theme, made in the theme editor");
+    if resp.clicked() && !active {
+        set_active(ui.ctx(), true);
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+// === Constants ===
+// The app's name, which eframe also keeps its storage under
+#[rustfmt::skip] pub const APP_ID: &str = "Texicon Widget Demo App";
+
+// ------
+// Export
+// ------
+// Copy an export to the clipboard and (natively) save it in the app's storage directory.
+// Returns a status message.
+pub fn export(ctx: &egui::Context, text: String, file_name: &str) -> String {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let saved = save(file_name, &text);
+        ctx.copy_text(text);
+        match saved {
+            Ok(path) => format!("Copied to the clipboard and saved to {}", path.display()),
+            Err(err) => format!("Copied to the clipboard, but saving {file_name} failed: {err}"),
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        ctx.copy_text(text);
+        format!("Copied {file_name} to the clipboard")
+    }
+}
+
+// Where an export called `file_name` is saved: next to eframe's app state,
+// rather than wherever the app was started from
+#[cfg(not(target_arch = "wasm32"))]
+pub fn path(file_name: &str) -> Option<PathBuf> {
+    eframe::storage_dir(APP_ID).map(|dir| dir.join(file_name))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save(file_name: &str, text: &str) -> Result<PathBuf, String> {
+    let path = path(file_name).ok_or("there is no storage directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+    std::fs::write(&path, text).map_err(|err| err.to_string())?;
+    Ok(path)
+}
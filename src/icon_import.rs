@@ -0,0 +1,118 @@
+use std::fmt;
+use std::path::Path;
+
+// === Constants ===
+// Bigger files are refused, so a stray photo can't stall the image loaders
+#[rustfmt::skip] pub const MAX_ICON_BYTES: usize = 1024 * 1024;
+#[rustfmt::skip] const PNG_SIGNATURE: &[u8]      = b"\x89PNG\r\n\x1a\n";
+
+// === Errors ===
+#[derive(Debug, PartialEq, Eq)]
+pub enum ImportError {
+    // Not an .svg or .png file
+    Unsupported { name: String },
+    TooLarge { name: String, bytes: usize },
+    Unreadable { name: String, message: String },
+    // The contents don't match the file extension
+    Invalid { name: String },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported { name } => {
+                write!(f, "{name}: only SVG and PNG files can be imported")
+            }
+            Self::TooLarge { name, bytes } => write!(
+                f,
+                "{name} is {} KiB, more than the {} KiB limit",
+                bytes.div_ceil(1024),
+                MAX_ICON_BYTES / 1024
+            ),
+            Self::Unreadable { name, message } => write!(f, "{name}: can't read it: {message}"),
+            Self::Invalid { name } => write!(f, "{name} isn't a valid SVG or PNG file"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+// ----------
+// Icon files
+// ----------
+// The name and contents of a dropped file: a path natively, the bytes themselves on the web.
+// Dropped icons are named by their file name, so they never clash with the built-in icons.
+pub fn read_dropped(file: &egui::DroppedFile) -> Result<(String, Vec<u8>), ImportError> {
+    let name = match (&file.path, file.name.is_empty()) {
+        (Some(path), true) => path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        _ => file.name.clone(),
+    };
+    let bytes = match (&file.bytes, &file.path) {
+        (Some(bytes), _) => bytes.to_vec(),
+        (None, Some(path)) => read(&name, path)?,
+        (None, None) => {
+            return Err(ImportError::Unreadable {
+                name,
+                message: "the file has no contents".to_owned(),
+            });
+        }
+    };
+    Ok((name, bytes))
+}
+
+// Whether the file name says SVG or PNG
+pub fn is_icon_file(name: &str) -> bool {
+    matches!(extension(name).as_deref(), Some("svg" | "png"))
+}
+
+fn extension(name: &str) -> Option<String> {
+    Path::new(name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+}
+
+pub fn read(name: &str, path: &Path) -> Result<Vec<u8>, ImportError> {
+    let unreadable = |err: std::io::Error| ImportError::Unreadable {
+        name: name.to_owned(),
+        message: err.to_string(),
+    };
+    // Check the size first, to avoid reading a huge file only to refuse it
+    let len = std::fs::metadata(path).map_err(unreadable)?.len();
+    let bytes = usize::try_from(len).unwrap_or(usize::MAX);
+    if bytes > MAX_ICON_BYTES {
+        return Err(ImportError::TooLarge {
+            name: name.to_owned(),
+            bytes,
+        });
+    }
+    std::fs::read(path).map_err(unreadable)
+}
+
+// The extension says what the file should be, and the contents have to agree
+pub fn check(name: &str, bytes: &[u8]) -> Result<(), ImportError> {
+    let valid: fn(&[u8]) -> bool = match extension(name).as_deref() {
+        Some("png") => |data| data.starts_with(PNG_SIGNATURE),
+        Some("svg") => |data| std::str::from_utf8(data).is_ok_and(|text| text.contains("<svg")),
+        _ => {
+            return Err(ImportError::Unsupported {
+                name: name.to_owned(),
+            });
+        }
+    };
+    if bytes.len() > MAX_ICON_BYTES {
+        return Err(ImportError::TooLarge {
+            name: name.to_owned(),
+            bytes: bytes.len(),
+        });
+    }
+    if valid(bytes) {
+        Ok(())
+    } else {
+        Err(ImportError::Invalid {
+            name: name.to_owned(),
+        })
+    }
+}
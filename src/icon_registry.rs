@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use egui::ImageSource;
+
+use crate::icon_import::{self, ImportError};
+use crate::menu_file::BUILTIN_ICONS;
+
+// === Constants ===
+// Names a directory of svg/png icons to add at startup (native only)
+#[rustfmt::skip] pub const ICON_DIR_ENV: &str = "TEXICON_ICONS";
+// An optional file in that directory tagging its icons, e.g. `{"rocket.svg": ["space"]}`
+#[rustfmt::skip] const TAGS_FILE: &str        = "tags.ron";
+#[rustfmt::skip] const URI_PREFIX: &str       = "bytes://icons/";
+
+// Tags of the built-in icons, besides the words of their names
+#[rustfmt::skip]
+const BUILTIN_TAGS: [(&str, &[&str]); 6] = [
+    ("testtube",   &["lab", "science", "experiment"]),
+    ("clock",      &["time", "timing"]),
+    ("waves",      &["signal", "filter"]),
+    ("gear",       &["settings", "config"]),
+    ("gear-light", &["settings", "config", "light"]),
+    ("article",    &["document", "text"]),
+];
+
+// Where an icon came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IconOrigin {
+    // Embedded in the app (the bundle on the web)
+    Builtin,
+    // Found in the icon directory at startup
+    Directory,
+    // Dropped on the app
+    Imported,
+}
+
+pub struct IconEntry {
+    pub name: String,
+    pub tags: Vec<String>,
+    pub origin: IconOrigin,
+    pub img: ImageSource<'static>,
+    // The file it was read from (absolute), for icons from the icon directory
+    pub path: Option<PathBuf>,
+}
+
+impl IconEntry {
+    // Every word of the query is part of the name or of a tag (ignoring case)
+    pub fn matches(&self, query: &str) -> bool {
+        let name = self.name.to_lowercase();
+        query.split_whitespace().all(|word| {
+            let word = word.to_lowercase();
+            name.contains(&word) || self.tags.iter().any(|tag| tag.contains(&word))
+        })
+    }
+}
+
+// -------------
+// Icon registry
+// -------------
+// Every icon the menus, playground and gallery can use, by name.
+// Files are registered with egui's image loaders, and replace any icon of the same name.
+pub struct IconRegistry {
+    icons: Vec<IconEntry>,
+    // Bumped on every change, so views of the registry know to rebuild
+    revision: u64,
+}
+
+impl Default for IconRegistry {
+    // Just the built-in icons
+    fn default() -> Self {
+        let icons = BUILTIN_ICONS
+            .iter()
+            .map(|(name, img)| {
+                let extra = BUILTIN_TAGS
+                    .iter()
+                    .find(|(icon, _)| icon == name)
+                    .map_or(&[][..], |(_, tags)| *tags);
+                let file = img.uri().unwrap_or(*name);
+                IconEntry {
+                    name: (*name).to_owned(),
+                    tags: tags_for(file, extra.iter().copied()),
+                    origin: IconOrigin::Builtin,
+                    img: img.clone(),
+                    path: None,
+                }
+            })
+            .collect();
+        Self { icons, revision: 0 }
+    }
+}
+
+impl IconRegistry {
+    // The built-in icons, plus the directory named by `TEXICON_ICONS` natively.
+    // Also returns the files that couldn't be added.
+    pub fn load(ctx: &egui::Context) -> (Self, Vec<ImportError>) {
+        let mut registry = Self::default();
+        let dir = if cfg!(target_arch = "wasm32") {
+            None
+        } else {
+            std::env::var_os(ICON_DIR_ENV)
+        };
+        let errors = match dir {
+            Some(dir) => registry.scan_dir(ctx, Path::new(&dir)),
+            None => Vec::new(),
+        };
+        (registry, errors)
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &IconEntry> {
+        self.icons.iter()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.icons.iter().map(|entry| entry.name.as_str())
+    }
+
+    pub fn icon(&self, name: &str) -> Option<ImageSource<'static>> {
+        self.entry(name).map(|entry| entry.img.clone())
+    }
+
+    pub fn entry(&self, name: &str) -> Option<&IconEntry> {
+        self.icons.iter().find(|entry| entry.name == name)
+    }
+
+    pub fn search<'a>(&'a self, query: &'a str) -> impl Iterator<Item = &'a IconEntry> {
+        self.icons.iter().filter(move |entry| entry.matches(query))
+    }
+
+    // Every tag, sorted and without repeats
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self
+            .icons
+            .iter()
+            .flat_map(|entry| entry.tags.iter().map(String::as_str))
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    // Import a file dropped on the app. Returns the name of the new icon.
+    pub fn import(
+        &mut self,
+        ctx: &egui::Context,
+        file: &egui::DroppedFile,
+    ) -> Result<String, ImportError> {
+        let (name, bytes) = icon_import::read_dropped(file)?;
+        self.add_file(ctx, &name, bytes, IconOrigin::Imported, &[], None)?;
+        Ok(name)
+    }
+
+    // Add every svg/png file in `dir`, tagged by its `tags.ron` if there is one.
+    // Other files are skipped. Returns the icons that couldn't be added.
+    pub fn scan_dir(&mut self, ctx: &egui::Context, dir: &Path) -> Vec<ImportError> {
+        let unreadable = |name: &Path, err: &dyn std::fmt::Display| ImportError::Unreadable {
+            name: name.display().to_string(),
+            message: err.to_string(),
+        };
+        let mut paths: Vec<_> = match std::fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .is_some_and(|name| icon_import::is_icon_file(&name.to_string_lossy()))
+                })
+                .collect(),
+            Err(err) => return vec![unreadable(dir, &err)],
+        };
+        paths.sort();
+
+        let mut errors = Vec::new();
+        let tags_path = dir.join(TAGS_FILE);
+        let tags: BTreeMap<String, Vec<String>> = match std::fs::read_to_string(&tags_path) {
+            Ok(source) => ron::from_str(&source).unwrap_or_else(|err| {
+                errors.push(unreadable(&tags_path, &err));
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+
+        for path in paths {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let extra = tags.get(&name).map_or(&[][..], Vec::as_slice);
+            let file = std::fs::canonicalize(&path).ok();
+            let added = icon_import::read(&name, &path).and_then(|bytes| {
+                let origin = IconOrigin::Directory;
+                self.add_file(ctx, &name, bytes, origin, extra, file.as_deref())
+            });
+            if let Err(err) = added {
+                errors.push(err);
+            }
+        }
+        errors
+    }
+
+    // Check an svg/png file and register it as `name`. `path` is where it was read from, if known.
+    pub fn add_file(
+        &mut self,
+        ctx: &egui::Context,
+        name: &str,
+        bytes: Vec<u8>,
+        origin: IconOrigin,
+        extra_tags: &[String],
+        path: Option<&Path>,
+    ) -> Result<(), ImportError> {
+        icon_import::check(name, &bytes)?;
+
+        let uri = format!("{URI_PREFIX}{name}");
+        // Drop what the loaders cached for an earlier file of the same name
+        ctx.forget_image(&uri);
+        ctx.include_bytes(uri.clone(), bytes);
+        let entry = IconEntry {
+            name: name.to_owned(),
+            tags: tags_for(name, extra_tags.iter().map(String::as_str)),
+            origin,
+            img: ImageSource::Uri(uri.into()),
+            path: path.map(Path::to_path_buf),
+        };
+        match self.icons.iter_mut().find(|existing| existing.name == name) {
+            Some(existing) => *existing = entry,
+            None => self.icons.push(entry),
+        }
+        self.revision += 1;
+        Ok(())
+    }
+}
+
+// The words of a file name and its type, plus `extra`, in lower case and without repeats
+fn tags_for<'a>(file: &str, extra: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let path = Path::new(file);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let words = stem
+        .split(['-', '_', ' ', '.'])
+        .filter(|word| !word.is_empty())
+        .map(str::to_owned);
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned());
+
+    let mut tags: Vec<String> = words
+        .chain(extension)
+        .chain(extra.map(str::to_owned))
+        .map(|tag| tag.to_lowercase())
+        .collect();
+    tags.sort_unstable();
+    tags.dedup();
+    tags
+}
@@ -1,5 +1,33 @@
+pub mod action;
+pub mod animation;
 mod app;
+pub mod auto_theme;
+pub mod badge;
+pub mod badge_demo;
+pub mod bench;
+pub mod bench_plot;
+pub mod custom_theme;
+pub mod export;
+pub mod icon_import;
+pub mod icon_registry;
+pub mod menu_file;
+pub mod menu_layout;
+pub mod page_experiments;
+pub mod page_filtering;
+pub mod page_gallery;
+pub mod page_playground;
+pub mod page_settings;
+pub mod page_theme_editor;
+pub mod page_timing;
+pub mod pages;
+pub mod palette;
+mod persistence;
+pub mod stress;
 pub use app::TexiconDemoApp;
 pub mod texi_central_menu;
+pub mod texi_export;
+pub mod texi_menu;
 pub mod texi_side_menu;
+pub mod texi_style;
 pub mod texi_top_menu;
+pub mod toast;
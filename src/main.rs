@@ -4,13 +4,28 @@
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result {
+    use egui_widget_texicon_demo_app::stress;
+
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    // `--stress <count> [--headless [--frames <n>]]`, see `stress::StressArgs`
+    let args = match stress::StressArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            log::error!("{err}");
+            stress::StressArgs::default()
+        }
+    };
+    if let (true, Some(count)) = (args.headless, args.count) {
+        run_stress_headless(count, args.frames.unwrap_or(HEADLESS_FRAMES));
+        return Ok(());
+    }
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_drag_and_drop(true)
-            .with_min_inner_size([1200.0, 800.0])
-            .with_max_inner_size([1600.0, 900.0])
+            .with_inner_size([1400.0, 900.0])
+            .with_min_inner_size([400.0, 300.0])
             .with_icon(
                 // NOTE: Adding an icon is optional
                 eframe::icon_data::from_png_bytes(&include_bytes!("../assets/icon-256.png")[..])
@@ -19,16 +34,48 @@ fn main() -> eframe::Result {
         ..Default::default()
     };
     eframe::run_native(
-        "Texicon Widget Demo App",
+        egui_widget_texicon_demo_app::export::APP_ID,
         native_options,
-        Box::new(|cc| {
+        Box::new(move |cc| {
             // This gives us image support:
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Ok(Box::new(egui_widget_texicon_demo_app::TexiconDemoApp::new()))
+            let app = egui_widget_texicon_demo_app::TexiconDemoApp::new(cc);
+            Ok(Box::new(match args.count {
+                Some(count) => app.with_stress(count),
+                None => app,
+            }))
         }),
     )
 }
 
+// Frames run by `--headless` when `--frames` isn't given
+#[cfg(not(target_arch = "wasm32"))]
+const HEADLESS_FRAMES: usize = 100;
+
+// Run the stress test without a window or GPU and print its timings as JSON
+#[cfg(not(target_arch = "wasm32"))]
+fn run_stress_headless(count: usize, frames: usize) {
+    use std::io::Write as _;
+
+    let ctx = egui::Context::default();
+    egui_extras::install_image_loaders(&ctx);
+    let report = egui_widget_texicon_demo_app::stress::run_headless(
+        &ctx,
+        count,
+        frames,
+        egui::vec2(1400.0, 900.0),
+    );
+    log::info!(
+        "{} texicons, {} frames, {} primitives in the last frame",
+        report.texicons,
+        frames,
+        report.primitives
+    );
+    if let Err(err) = writeln!(std::io::stdout(), "{}", report.to_json()) {
+        log::error!("Failed to write the report: {err}");
+    }
+}
+
 // When compiling to web using trunk:
 #[cfg(target_arch = "wasm32")]
 fn main() {
@@ -58,7 +105,9 @@ fn main() {
                 Box::new(|cc| {
                     // This gives us image support:
                     egui_extras::install_image_loaders(&cc.egui_ctx);
-                    Ok(Box::new(egui_widget_texicon_demo_app::TexiconDemoApp::new()))
+                    Ok(Box::new(egui_widget_texicon_demo_app::TexiconDemoApp::new(
+                        cc,
+                    )))
                 }),
             )
             .await;
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use egui::{include_image, ImageSource};
+
+use crate::action::{Action, ContextEntry};
+use crate::badge::Badge;
+use crate::menu_layout::{ItemRef, MenuSlot};
+use crate::texi_menu::{ItemStyle, MyTexicon};
+
+// The built-in menus, used when no menu file is given or it fails to load
+const BUILTIN_MENUS: &str = include_str!("../assets/menus.ron");
+
+// Names a menu file to load at startup instead of the built-in menus (native only)
+pub const MENU_FILE_ENV: &str = "TEXICON_MENUS";
+
+// === Built-in icons ===
+#[rustfmt::skip]
+pub const BUILTIN_ICONS: [(&str, ImageSource<'static>); 6] = [
+    ("testtube",   include_image!("../assets/pics/testtube.svg")),
+    ("clock",      include_image!("../assets/pics/clock.svg")),
+    ("waves",      include_image!("../assets/pics/waves.svg")),
+    ("gear",       include_image!("../assets/pics/gear.svg")),
+    ("gear-light", include_image!("../assets/pics/gear-light.svg")),
+    ("article",    include_image!("../assets/pics/article.png")),
+];
+
+pub fn builtin_icon(name: &str) -> Option<ImageSource<'static>> {
+    BUILTIN_ICONS
+        .iter()
+        .find(|(icon_name, _)| *icon_name == name)
+        .map(|(_, img)| img.clone())
+}
+
+// === File format ===
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MenuFile {
+    top: Vec<ItemDef>,
+    side: Vec<ItemDef>,
+    central: Vec<ItemDef>,
+}
+
+#[rustfmt::skip]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ItemDef {
+    icon:    IconDef,
+    text:    String,
+    #[serde(default)]
+    tooltip: String,
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    #[serde(default)]
+    action:  Option<Action>,
+    #[serde(default)]
+    style:   ItemStyle,
+    #[serde(default)]
+    badge:   Option<Badge>,
+    #[serde(default)]
+    context: Vec<ContextEntry>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(serde::Deserialize)]
+enum IconDef {
+    // One of the icons embedded in the app, by name
+    Builtin(String),
+    // An svg/png file, relative to the menu file
+    File(PathBuf),
+}
+
+// === Errors ===
+#[derive(Debug)]
+pub enum MenuError {
+    // The menu file couldn't be read
+    Io {
+        path: String,
+        message: String,
+    },
+    // The menu file isn't valid RON, or doesn't match the expected layout
+    Parse {
+        path: String,
+        message: String,
+    },
+    // An item parsed fine but can't be used
+    Item {
+        path: String,
+        menu: &'static str,
+        index: usize,
+        text: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for MenuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, message } => write!(f, "{path}: can't read menu file: {message}"),
+            Self::Parse { path, message } => write!(f, "{path}:{message}"),
+            Self::Item {
+                path,
+                menu,
+                index,
+                text,
+                message,
+            } => write!(f, "{path}: {menu} menu, item {index} ({text:?}): {message}"),
+        }
+    }
+}
+
+impl std::error::Error for MenuError {}
+
+// === Menus ===
+#[derive(Clone)]
+pub struct Menus {
+    pub top: Vec<MyTexicon>,
+    pub side: Vec<MyTexicon>,
+    pub central: Vec<MyTexicon>,
+}
+
+impl Menus {
+    pub fn builtin() -> Self {
+        Self::from_ron(BUILTIN_MENUS, "assets/menus.ron", None)
+            .expect("The built-in menu file is valid")
+    }
+
+    // Load the menu file named by `TEXICON_MENUS`, if set.
+    // Falls back to the built-in menus and returns the error if that fails.
+    pub fn load() -> (Self, Option<MenuError>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = std::env::var_os(MENU_FILE_ENV) {
+            match Self::from_file(Path::new(&path)) {
+                Ok(menus) => return (menus, None),
+                Err(err) => {
+                    log::error!("{err}");
+                    return (Self::builtin(), Some(err));
+                }
+            }
+        }
+        (Self::builtin(), None)
+    }
+
+    // Load menus from a RON file. File icons are resolved relative to its directory.
+    pub fn from_file(path: &Path) -> Result<Self, MenuError> {
+        let source = std::fs::read_to_string(path).map_err(|err| MenuError::Io {
+            path: path.display().to_string(),
+            message: err.to_string(),
+        })?;
+        Self::from_ron(&source, &path.display().to_string(), path.parent())
+    }
+
+    // Parse menus from RON source. `path` is only used in error messages.
+    pub fn from_ron(source: &str, path: &str, base_dir: Option<&Path>) -> Result<Self, MenuError> {
+        let file: MenuFile = ron::from_str(source).map_err(|err| MenuError::Parse {
+            path: path.to_owned(),
+            message: err.to_string(),
+        })?;
+
+        let build = |menu: &'static str, slot: MenuSlot, items: Vec<ItemDef>| {
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    let origin = ItemRef {
+                        menu: slot,
+                        index,
+                        text: item.text.clone(),
+                    };
+                    build_item(item, origin, base_dir).map_err(|(text, message)| MenuError::Item {
+                        path: path.to_owned(),
+                        menu,
+                        index,
+                        text,
+                        message,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            top: build("top", MenuSlot::Top, file.top)?,
+            side: build("side", MenuSlot::Side, file.side)?,
+            central: build("central", MenuSlot::Central, file.central)?,
+        })
+    }
+
+    pub fn slot(&self, slot: MenuSlot) -> &[MyTexicon] {
+        match slot {
+            MenuSlot::Top => &self.top,
+            MenuSlot::Side => &self.side,
+            MenuSlot::Central => &self.central,
+        }
+    }
+}
+
+// Turn a parsed item into a texicon, or return its text and what's wrong with it
+fn build_item(
+    item: ItemDef,
+    origin: ItemRef,
+    base_dir: Option<&Path>,
+) -> Result<MyTexicon, (String, String)> {
+    let img = match resolve_icon(&item.icon, base_dir) {
+        Ok(img) => img,
+        Err(message) => return Err((item.text, message)),
+    };
+    if let Some(message) = check_style(&item.style) {
+        return Err((item.text, message));
+    }
+    Ok(MyTexicon {
+        img,
+        text: item.text,
+        tooltip: item.tooltip,
+        enabled: item.enabled,
+        action: item.action,
+        style: item.style,
+        badge: item.badge,
+        context: item.context,
+        origin: Some(origin),
+    })
+}
+
+fn resolve_icon(icon: &IconDef, base_dir: Option<&Path>) -> Result<ImageSource<'static>, String> {
+    match icon {
+        IconDef::Builtin(name) => builtin_icon(name).ok_or_else(|| {
+            let names: Vec<&str> = BUILTIN_ICONS.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown built-in icon {name:?} (expected one of: {})",
+                names.join(", ")
+            )
+        }),
+        IconDef::File(file) => {
+            let path = match base_dir {
+                Some(dir) => dir.join(file),
+                None => file.clone(),
+            };
+            // Absolute, so the icon doesn't depend on the working directory
+            let absolute = std::fs::canonicalize(&path)
+                .ok()
+                .filter(|absolute| absolute.is_file())
+                .ok_or_else(|| format!("icon file {} not found", path.display()))?;
+            Ok(ImageSource::Uri(file_uri(&absolute).into()))
+        }
+    }
+}
+
+// The `file://` URI of an absolute path. On Windows that's without the `\\?\` prefix
+// `canonicalize` adds, and with forward slashes.
+pub fn file_uri(path: &Path) -> String {
+    let path = path.display().to_string();
+    #[cfg(windows)]
+    let path = path
+        .strip_prefix(r"\\?\")
+        .unwrap_or(&path)
+        .replace('\\', "/");
+    format!("file://{path}")
+}
+
+fn check_style(style: &ItemStyle) -> Option<String> {
+    let invalid = |value: f32| !value.is_finite() || value < 0.0;
+
+    let sizes = [
+        ("img_size", style.img_size),
+        ("frame_size", style.frame_size),
+    ];
+    for (name, size) in sizes {
+        if let Some([x, y]) = size.filter(|&[x, y]| invalid(x) || invalid(y)) {
+            return Some(format!(
+                "style.{name} must be finite and not negative, got ({x}, {y})"
+            ));
+        }
+    }
+    let values = [
+        ("img_scale_hov", style.img_scale_hov),
+        ("text_size", style.text_size),
+        ("img_text_gap", style.img_text_gap),
+        ("frame_width", style.frame_width),
+        ("tooltip_gap", style.tooltip_gap),
+    ];
+    for (name, value) in values {
+        if let Some(value) = value.filter(|&value| invalid(value)) {
+            return Some(format!(
+                "style.{name} must be finite and not negative, got {value}"
+            ));
+        }
+    }
+    None
+}
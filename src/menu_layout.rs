@@ -0,0 +1,100 @@
+use crate::menu_file::Menus;
+use crate::texi_menu::{MyTexicon, TexiMenu};
+
+// Which of the app's menus
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub enum MenuSlot {
+    Top,
+    Side,
+    Central,
+}
+
+// ---------
+// Item refs
+// ---------
+// A texicon of the menus as loaded, by the menu it was defined in and its index there.
+// The text guards against a menu file that changed since the layout was saved.
+#[rustfmt::skip]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct ItemRef {
+    pub menu:  MenuSlot,
+    pub index: usize,
+    pub text:  String,
+}
+
+// -----------
+// Menu layout
+// -----------
+// Which texicons each menu shows, in order, after reordering and dragging between menus.
+// Saved with the app state, and applied to the menus as loaded on the next start.
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct MenuLayout {
+    pub top:     Vec<ItemRef>,
+    pub side:    Vec<ItemRef>,
+    pub central: Vec<ItemRef>,
+}
+
+impl MenuLayout {
+    // The layout of the menus as they are now.
+    // Texicons that weren't loaded from the menus (e.g. from the gallery) are left out.
+    pub fn of(top: &TexiMenu, side: &TexiMenu, central: &TexiMenu) -> Self {
+        let refs = |menu: &TexiMenu| {
+            menu.items()
+                .iter()
+                .filter_map(|texicon| texicon.origin.clone())
+                .collect()
+        };
+        Self {
+            top: refs(top),
+            side: refs(side),
+            central: refs(central),
+        }
+    }
+
+    // The index of `menu`'s selected texicon in its layout, which may leave texicons
+    // out before it. The first texicon if it is left out itself, or nothing is selected.
+    pub fn selected_index(menu: &TexiMenu) -> usize {
+        let Some(selected) = menu.selected_texicon() else {
+            return 0;
+        };
+        let items = menu.items();
+        if items
+            .get(selected)
+            .is_none_or(|texicon| texicon.origin.is_none())
+        {
+            return 0;
+        }
+        items
+            .iter()
+            .take(selected)
+            .filter(|texicon| texicon.origin.is_some())
+            .count()
+    }
+
+    // The texicons of each menu in this layout, taken from the menus as loaded.
+    // `None` if any ref doesn't match them.
+    pub fn items(&self, loaded: &Menus) -> Option<Menus> {
+        let items = |slot: MenuSlot, refs: &[ItemRef]| {
+            refs.iter()
+                .map(|item_ref| {
+                    let texicon = loaded
+                        .slot(item_ref.menu)
+                        .get(item_ref.index)
+                        .filter(|texicon| texicon.text == item_ref.text)?;
+                    // Texicons from another menu are pinned copies, as when they were dropped
+                    Some(if item_ref.menu == slot {
+                        texicon.clone()
+                    } else {
+                        texicon.pinned()
+                    })
+                })
+                .collect::<Option<Vec<MyTexicon>>>()
+        };
+        Some(Menus {
+            top: items(MenuSlot::Top, &self.top)?,
+            side: items(MenuSlot::Side, &self.side)?,
+            central: items(MenuSlot::Central, &self.central)?,
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use crate::pages::{Page, PageContext, PageId};
+
+// -----------
+// Experiments
+// -----------
+// What texicons can do, and the central menu to try it on
+pub struct ExperimentsPage;
+
+impl Page for ExperimentsPage {
+    fn id(&self) -> PageId {
+        PageId::Experiments
+    }
+
+    fn title(&self) -> &str {
+        "Experiments"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        crate::app::print_intro(ui);
+        ui.label(format!(
+            "Last texicon event: {}",
+            cx.last_event.unwrap_or("none yet")
+        ));
+        cx.events.extend(cx.central_menu.draw_texicons(ui));
+    }
+}
@@ -0,0 +1,46 @@
+use crate::pages::{Page, PageContext, PageId};
+use crate::texi_menu::TexiMenu;
+
+// ---------
+// Filtering
+// ---------
+// Search the texicons of every menu by their text
+#[derive(Default)]
+pub struct FilteringPage {
+    query: String,
+    enabled_only: bool,
+}
+
+impl FilteringPage {
+    fn matches(&self, menu: &TexiMenu, idx: usize) -> bool {
+        let text = menu.item_text(idx).unwrap_or_default().to_lowercase();
+        text.contains(&self.query.to_lowercase()) && (!self.enabled_only || menu.is_enabled(idx))
+    }
+}
+
+impl Page for FilteringPage {
+    fn id(&self) -> PageId {
+        PageId::Filtering
+    }
+
+    fn title(&self) -> &str {
+        "Filtering"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        ui.horizontal(|ui| {
+            ui.label("Text contains:");
+            ui.text_edit_singleline(&mut self.query);
+            ui.checkbox(&mut self.enabled_only, "Enabled only");
+        });
+        ui.add_space(10.);
+
+        for menu in [&*cx.top_menu, &*cx.side_menu, &*cx.central_menu] {
+            let found: Vec<&str> = (0..menu.item_count())
+                .filter(|&idx| self.matches(menu, idx))
+                .filter_map(|idx| menu.item_text(idx))
+                .collect();
+            ui.label(format!("{}: {}", menu.name(), found.join(", ")));
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use egui::vec2;
+
+use crate::action::TexiEventKind;
+use crate::icon_registry::IconRegistry;
+use crate::pages::{Page, PageContext, PageId};
+use crate::texi_menu::{ItemStyle, MyTexicon, Overflow, TexiLayout, TexiMenu};
+use crate::texi_style::TexiStyle;
+
+// === Constants ===
+#[rustfmt::skip] const TEXI_WIDTH: f32  = 90.0;
+#[rustfmt::skip] const TEXI_HEIGHT: f32 = 100.0;
+#[rustfmt::skip] const TEXI_GAP: f32    = 16.0;
+
+// === Style ===
+#[rustfmt::skip]
+const GALLERY: TexiStyle = TexiStyle {
+    name: "Gallery",
+    ..crate::texi_central_menu::ROUNDED
+};
+
+// A menu of the app, to put gallery icons on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MenuName {
+    Top,
+    Side,
+    Central,
+}
+
+impl MenuName {
+    const ALL: [Self; 3] = [Self::Top, Self::Side, Self::Central];
+
+    fn menu<'m>(self, cx: &'m mut PageContext<'_>) -> &'m mut TexiMenu {
+        match self {
+            Self::Top => &mut *cx.top_menu,
+            Self::Side => &mut *cx.side_menu,
+            Self::Central => &mut *cx.central_menu,
+        }
+    }
+}
+
+// -------
+// Gallery
+// -------
+// Every icon in the registry as a texicon, to search, audit under each theme,
+// and put on a menu slot with one click
+pub struct GalleryPage {
+    query: String,
+    // What the gallery menu was built from: the registry revision and the query
+    built: Option<(u64, String)>,
+    // Icon names, in gallery order
+    shown: Vec<String>,
+    menu: TexiMenu,
+    // Clicking an icon puts it on this texicon, found by its stable id wherever it moves
+    target: Option<(MenuName, egui::Id)>,
+    status: Option<String>,
+}
+
+impl Default for GalleryPage {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            built: None,
+            shown: Vec::new(),
+            menu: build_menu(Vec::new()),
+            target: None,
+            status: None,
+        }
+    }
+}
+
+impl GalleryPage {
+    fn rebuild(&mut self, icons: &IconRegistry) {
+        let built = (icons.revision(), self.query.clone());
+        if self.built.as_ref() == Some(&built) {
+            return;
+        }
+        let entries: Vec<_> = icons.search(&self.query).collect();
+        self.shown = entries.iter().map(|entry| entry.name.clone()).collect();
+        self.menu = build_menu(
+            entries
+                .into_iter()
+                .map(|entry| MyTexicon {
+                    img: entry.img.clone(),
+                    text: entry.name.clone(),
+                    tooltip: format!("{:?} icon, tagged {}", entry.origin, entry.tags.join(", ")),
+                    enabled: true,
+                    action: None,
+                    style: ItemStyle::default(),
+                    badge: None,
+                    context: Vec::new(),
+                    origin: None,
+                })
+                .collect(),
+        );
+        self.built = Some(built);
+    }
+
+    fn search(&mut self, ui: &mut egui::Ui, icons: &IconRegistry) {
+        ui.horizontal(|ui| {
+            ui.label("Search:");
+            ui.add(egui::TextEdit::singleline(&mut self.query).hint_text("name or tag"));
+            if ui.button("Clear").clicked() {
+                self.query.clear();
+            }
+            ui.label(format!(
+                "{} of {} icons",
+                self.shown.len(),
+                icons.entries().count()
+            ));
+        });
+
+        // Clicking a tag adds it to the search, or takes it out again
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Tags:");
+            let mut words: Vec<String> = self.query.split_whitespace().map(str::to_owned).collect();
+            for tag in icons.tags() {
+                let position = words.iter().position(|word| word.eq_ignore_ascii_case(tag));
+                if ui.selectable_label(position.is_some(), tag).clicked() {
+                    match position {
+                        Some(idx) => {
+                            words.remove(idx);
+                        }
+                        None => words.push(tag.to_owned()),
+                    }
+                    self.query = words.join(" ");
+                }
+            }
+        });
+    }
+
+    // Pick the texicon that clicked icons go to
+    fn target(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        let describe = |cx: &mut PageContext<'_>, (name, id): (MenuName, egui::Id)| {
+            let menu = name.menu(cx);
+            let text = menu
+                .item_with_stable_id(id)
+                .and_then(|idx| menu.item_text(idx));
+            format!("{}: {}", menu.name(), text.unwrap_or_default())
+        };
+        ui.horizontal(|ui| {
+            ui.label("Clicking an icon puts it on:");
+            let selected = self
+                .target
+                .map_or_else(|| "nothing".to_owned(), |target| describe(cx, target));
+            egui::ComboBox::from_id_salt("gallery_target")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.target, None, "nothing");
+                    for name in MenuName::ALL {
+                        let ids: Vec<egui::Id> = name
+                            .menu(cx)
+                            .items()
+                            .iter()
+                            .map(MyTexicon::stable_id)
+                            .collect();
+                        for id in ids {
+                            let text = describe(cx, (name, id));
+                            ui.selectable_value(&mut self.target, Some((name, id)), text);
+                        }
+                    }
+                });
+            if let Some(status) = &self.status {
+                ui.label(status);
+            }
+        });
+    }
+
+    fn gallery(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        let events = egui::ScrollArea::vertical()
+            .id_salt("gallery")
+            .auto_shrink(false)
+            .show(ui, |ui| {
+                let width = ui.available_width();
+                let size = vec2(width, self.menu.wrapped_height(width));
+                ui.allocate_ui(size, |ui| self.menu.draw_texicons(ui)).inner
+            })
+            .inner;
+
+        let clicked = events
+            .iter()
+            .rev()
+            .find(|event| event.kind == TexiEventKind::Activated)
+            .and_then(|event| self.shown.get(event.item));
+        if let (Some(icon), Some((name, id))) = (clicked, self.target) {
+            let img = cx.icons.icon(icon);
+            let menu = name.menu(cx);
+            if let (Some(img), Some(idx)) = (img, menu.item_with_stable_id(id)) {
+                menu.set_image(idx, img);
+                self.status = Some(format!(
+                    "Put {icon} on {}: {}",
+                    menu.name(),
+                    menu.item_text(idx).unwrap_or_default()
+                ));
+            }
+        }
+    }
+}
+
+impl Page for GalleryPage {
+    fn id(&self) -> PageId {
+        PageId::Gallery
+    }
+
+    fn title(&self) -> &str {
+        "Icon gallery"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        self.search(ui, cx.icons);
+        self.rebuild(cx.icons);
+        self.target(ui, cx);
+        ui.separator();
+        self.gallery(ui, cx);
+    }
+}
+
+fn build_menu(items: Vec<MyTexicon>) -> TexiMenu {
+    TexiMenu::new(
+        items,
+        TexiLayout::Horizontal,
+        vec2(TEXI_WIDTH, TEXI_HEIGHT),
+        TEXI_GAP,
+        vec![GALLERY],
+    )
+    .label("Icon gallery")
+    .overflow(Overflow::Wrap)
+}
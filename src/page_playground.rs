@@ -0,0 +1,640 @@
+use egui::{vec2, Color32, ImageSource, RectAlign, Vec2};
+use egui_widget_texicon::Texicon;
+
+use crate::export::export;
+use crate::icon_registry::IconRegistry;
+use crate::pages::{Page, PageContext, PageId};
+use crate::palette::{Palette, Role, Shade};
+use crate::texi_export;
+use crate::texi_style::{
+    tooltip_position_name, SenseArea, StateShades, TexiStyle, TOOLTIP_POSITIONS,
+};
+
+// === Constants ===
+#[rustfmt::skip] const PREVIEW_HEIGHT: f32     = 220.0;
+#[rustfmt::skip] const DEFAULT_FRAME: Vec2     = vec2(120.0, 120.0);
+#[rustfmt::skip] const ROLE_COMBO_WIDTH: f32   = 80.0;
+#[rustfmt::skip] const MAX_IMG_SIZE: f32       = 200.0;
+#[rustfmt::skip] const MAX_FRAME_SIZE: f32     = 300.0;
+#[rustfmt::skip] const MAX_TEXT_SIZE: f32      = 40.0;
+#[rustfmt::skip] const MAX_GAP: f32            = 60.0;
+#[rustfmt::skip] const MAX_FRAME_WIDTH: f32    = 10.0;
+#[rustfmt::skip] const MAX_RADIUS: u8          = 60;
+#[rustfmt::skip] const MAX_TOOLTIP_GAP: f32    = 80.0;
+
+// The presets to start from
+#[rustfmt::skip]
+const STYLES: [TexiStyle; 7] = [
+    crate::texi_top_menu::TOP_MENU,
+    crate::texi_side_menu::SIDE_MENU,
+    crate::texi_central_menu::POSTER,
+    crate::texi_central_menu::GHOST,
+    crate::texi_central_menu::ROUNDED,
+    crate::texi_central_menu::WIDE,
+    crate::texi_central_menu::MUTED,
+];
+
+// -------
+// Colours
+// -------
+// A colour slot: a palette role that follows the theme, or a fixed colour
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Paint {
+    Role(Role),
+    Custom(Color32),
+}
+
+impl Paint {
+    // Faded shades have no role of their own, so they become fixed colours
+    pub fn from_shade(shade: Shade, palette: &Palette) -> Self {
+        if shade.factor == 1.0 {
+            Self::Role(shade.role)
+        } else {
+            Self::Custom(palette.shade(shade))
+        }
+    }
+
+    pub fn color(self, palette: &Palette) -> Color32 {
+        match self {
+            Self::Role(role) => palette.get(role),
+            Self::Custom(color) => color,
+        }
+    }
+}
+
+// Colours for the normal, selected and hovered states
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StatePaints {
+    pub normal: Paint,
+    pub sel: Paint,
+    pub hov: Paint,
+}
+
+impl StatePaints {
+    pub const fn new(normal: Paint, sel: Paint, hov: Paint) -> Self {
+        Self { normal, sel, hov }
+    }
+
+    pub fn from_shades(shades: StateShades, palette: &Palette) -> Self {
+        Self::new(
+            Paint::from_shade(shades.normal, palette),
+            Paint::from_shade(shades.sel, palette),
+            Paint::from_shade(shades.hov, palette),
+        )
+    }
+
+    // The hovered colour in every state, to preview hovering without the mouse
+    fn hovered(self) -> Self {
+        Self::new(self.hov, self.hov, self.hov)
+    }
+}
+
+// ----------------------
+// The playground texicon
+// ----------------------
+// Every builder option of one texicon. `None` leaves the texicon's own default in place.
+#[rustfmt::skip]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaygroundTexicon {
+    // One of the built-in icons, by name
+    pub icon:             String,
+    pub text:             String,
+    pub show_text:        bool,
+    pub tooltip:          String,
+    pub img_size:         Option<Vec2>,
+    pub img_scale_hov:    Option<f32>,
+    pub text_size:        Option<f32>,
+    pub img_text_gap:     Option<f32>,
+    pub sense:            Option<SenseArea>,
+    pub bkgnd_col:        StatePaints,
+    pub img_tint_col:     StatePaints,
+    pub text_col:         StatePaints,
+    pub frame_col:        StatePaints,
+    pub frame_size:       Option<Vec2>,
+    pub frame_width:      f32,
+    pub radius:           Option<u8>,
+    pub tooltip_gap:      f32,
+    pub tooltip_position: Option<RectAlign>,
+    pub enabled:          bool,
+    pub selected:         bool,
+}
+
+impl Default for PlaygroundTexicon {
+    #[rustfmt::skip]
+    fn default() -> Self {
+        let role = Paint::Role;
+        Self {
+            icon:             "testtube".to_owned(),
+            text:             "Playground".to_owned(),
+            show_text:        true,
+            tooltip:          "This is a tooltip for the playground texicon.".to_owned(),
+            img_size:         Some(vec2(40., 40.)),
+            img_scale_hov:    Some(1.15),
+            text_size:        Some(15.),
+            img_text_gap:     Some(10.),
+            sense:            None,
+            bkgnd_col:        StatePaints::new(role(Role::Base), role(Role::Mantle), role(Role::Crust)),
+            img_tint_col:     StatePaints::new(role(Role::Blue), role(Role::Blue), role(Role::Blue)),
+            text_col:         StatePaints::new(role(Role::Teal), role(Role::Teal), role(Role::Teal)),
+            frame_col:        StatePaints::new(role(Role::Crust), role(Role::Subtext0), role(Role::Subtext0)),
+            frame_size:       Some(DEFAULT_FRAME),
+            frame_width:      2.,
+            radius:           Some(20),
+            tooltip_gap:      20.,
+            tooltip_position: Some(RectAlign::BOTTOM),
+            enabled:          true,
+            selected:         false,
+        }
+    }
+}
+
+impl PlaygroundTexicon {
+    // Take over the look of a preset, keeping the image, text and state
+    pub fn set_style(&mut self, style: &TexiStyle, palette: &Palette) {
+        self.show_text = style.show_text;
+        self.img_size = style.img_size;
+        self.img_scale_hov = style.img_scale_hov;
+        self.text_size = style.text_size;
+        self.img_text_gap = style.img_text_gap;
+        self.sense = style.sense;
+        self.bkgnd_col = StatePaints::from_shades(style.bkgnd_col, palette);
+        self.img_tint_col = StatePaints::from_shades(style.img_tint_col, palette);
+        self.text_col = StatePaints::from_shades(style.text_col, palette);
+        self.frame_col = StatePaints::from_shades(style.frame_col, palette);
+        self.frame_size = style.frame_size;
+        self.frame_width = style.frame_width;
+        self.radius = style.radius;
+        self.tooltip_gap = style.tooltip_gap;
+        self.tooltip_position = style.tooltip_position;
+    }
+
+    // The texicon as configured, showing `img` (the icon named in `icon`).
+    // With `force_hover` it looks hovered without the mouse:
+    // hovered colours everywhere, and the image grown when it has an explicit size.
+    pub fn texicon(
+        &self,
+        img: ImageSource<'static>,
+        palette: &Palette,
+        force_hover: bool,
+    ) -> Texicon<'static> {
+        let (bkgnd, tint, text, frame) = if force_hover {
+            (
+                self.bkgnd_col.hovered(),
+                self.img_tint_col.hovered(),
+                self.text_col.hovered(),
+                self.frame_col.hovered(),
+            )
+        } else {
+            (
+                self.bkgnd_col,
+                self.img_tint_col,
+                self.text_col,
+                self.frame_col,
+            )
+        };
+
+        let mut texi = Texicon::new(img)
+            .enabled(self.enabled)
+            .selected(self.selected)
+            .bkgnd_col(bkgnd.normal.color(palette))
+            .bkgnd_col_sel(bkgnd.sel.color(palette))
+            .bkgnd_col_hov(bkgnd.hov.color(palette))
+            .img_tint_col(tint.normal.color(palette))
+            .img_tint_col_sel(tint.sel.color(palette))
+            .img_tint_col_hov(tint.hov.color(palette))
+            .text_col(text.normal.color(palette))
+            .text_col_sel(text.sel.color(palette))
+            .text_col_hov(text.hov.color(palette))
+            .frame_col(frame.normal.color(palette))
+            .frame_col_sel(frame.sel.color(palette))
+            .frame_col_hov(frame.hov.color(palette))
+            .frame_width(self.frame_width)
+            .tooltip_gap(self.tooltip_gap)
+            .tooltip_text(self.tooltip.clone());
+
+        if self.show_text {
+            texi = texi.text(self.text.clone());
+        }
+        let img_scale_hov = self.img_scale_hov.unwrap_or(1.0);
+        match self.img_size {
+            Some(size) if force_hover => texi = texi.img_size(size * img_scale_hov),
+            Some(size) => texi = texi.img_size(size),
+            None => {}
+        }
+        if let Some(scale) = self.img_scale_hov {
+            texi = texi.img_scale_hov(if force_hover { 1.0 } else { scale });
+        }
+        if let Some(size) = self.text_size {
+            texi = texi.text_size(size);
+        }
+        if let Some(gap) = self.img_text_gap {
+            texi = texi.img_text_gap(gap);
+        }
+        if let Some(sense) = self.sense {
+            texi = texi.sense(sense.texi_sense());
+        }
+        if let Some(size) = self.frame_size {
+            texi = texi.frame_size(size);
+        }
+        if let Some(radius) = self.radius {
+            texi = texi.radius(radius);
+        }
+        if let Some(position) = self.tooltip_position {
+            texi = texi.tooltip_position(position);
+        }
+        texi
+    }
+}
+
+// ----------
+// Playground
+// ----------
+// A live preview texicon with an editor for every builder option
+#[derive(Default)]
+pub struct PlaygroundPage {
+    texicon: PlaygroundTexicon,
+    force_hover: bool,
+    // RON or JSON pasted in to load, and how the last export or load went
+    import: String,
+    status: Option<String>,
+}
+
+impl PlaygroundPage {
+    pub fn texicon(&self) -> &PlaygroundTexicon {
+        &self.texicon
+    }
+
+    fn preview(&mut self, ui: &mut egui::Ui, palette: &Palette, icons: &IconRegistry) {
+        let (_id, rect) = ui.allocate_space(vec2(ui.available_width(), PREVIEW_HEIGHT));
+        ui.painter().rect_filled(rect, 8, palette.mantle);
+
+        let size = self.texicon.frame_size.unwrap_or(DEFAULT_FRAME);
+        match icons.icon(&self.texicon.icon) {
+            Some(img) => {
+                let texi = self.texicon.texicon(img, palette, self.force_hover);
+                // Clicking the preview selects it, like a texicon in a menu
+                if ui
+                    .put(egui::Rect::from_center_size(rect.center(), size), texi)
+                    .clicked()
+                {
+                    self.texicon.selected = !self.texicon.selected;
+                }
+            }
+            None => {
+                let text = format!("No icon called {:?}", self.texicon.icon);
+                ui.put(rect, egui::Label::new(text));
+            }
+        }
+    }
+
+    fn state_editor(&mut self, ui: &mut egui::Ui, palette: &Palette) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.texicon.enabled, "Enabled");
+            ui.checkbox(&mut self.texicon.selected, "Selected");
+            ui.checkbox(&mut self.force_hover, "Hovered");
+            ui.separator();
+
+            let mut style = None;
+            egui::ComboBox::from_id_salt("playground_style")
+                .selected_text("Start from a preset")
+                .show_ui(ui, |ui| {
+                    for preset in &STYLES {
+                        if ui.selectable_label(false, preset.name).clicked() {
+                            style = Some(*preset);
+                        }
+                    }
+                });
+            if let Some(style) = style {
+                self.texicon.set_style(&style, palette);
+            }
+            if ui.button("Reset").clicked() {
+                self.texicon = PlaygroundTexicon::default();
+            }
+        });
+    }
+
+    fn content_editor(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        let texicon = &mut self.texicon;
+        egui::Grid::new("playground_content")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Image");
+                ui.horizontal(|ui| {
+                    let names = cx.icons.names();
+                    egui::ComboBox::from_id_salt("playground_icon")
+                        .selected_text(texicon.icon.as_str())
+                        .show_ui(ui, |ui| {
+                            for name in names {
+                                ui.selectable_value(&mut texicon.icon, name.to_owned(), name);
+                            }
+                        });
+                    use_in_menu(ui, cx, &texicon.icon);
+                });
+                ui.end_row();
+
+                ui.checkbox(&mut texicon.show_text, "Text");
+                ui.add_enabled(
+                    texicon.show_text,
+                    egui::TextEdit::singleline(&mut texicon.text),
+                );
+                ui.end_row();
+
+                optional(ui, "Text size", &mut texicon.text_size, 13., |ui, size| {
+                    ui.add(egui::Slider::new(size, 6.0..=MAX_TEXT_SIZE));
+                });
+                optional(
+                    ui,
+                    "Image size",
+                    &mut texicon.img_size,
+                    vec2(40., 40.),
+                    |ui, size| {
+                        edit_size(ui, size, MAX_IMG_SIZE);
+                    },
+                );
+                optional(
+                    ui,
+                    "Image scale on hover",
+                    &mut texicon.img_scale_hov,
+                    1.1,
+                    |ui, scale| {
+                        ui.add(egui::Slider::new(scale, 0.5..=2.0));
+                    },
+                );
+                optional(
+                    ui,
+                    "Image to text gap",
+                    &mut texicon.img_text_gap,
+                    6.,
+                    |ui, gap| {
+                        ui.add(egui::Slider::new(gap, 0.0..=MAX_GAP));
+                    },
+                );
+            });
+    }
+
+    fn colour_editor(&mut self, ui: &mut egui::Ui, palette: &Palette) {
+        let texicon = &mut self.texicon;
+        egui::Grid::new("playground_colours")
+            .num_columns(4)
+            .show(ui, |ui| {
+                ui.label("");
+                ui.label("Normal");
+                ui.label("Selected");
+                ui.label("Hovered");
+                ui.end_row();
+
+                for (name, paints) in [
+                    ("Background", &mut texicon.bkgnd_col),
+                    ("Image tint", &mut texicon.img_tint_col),
+                    ("Text", &mut texicon.text_col),
+                    ("Frame", &mut texicon.frame_col),
+                ] {
+                    ui.label(name);
+                    for (state, paint) in [
+                        ("normal", &mut paints.normal),
+                        ("sel", &mut paints.sel),
+                        ("hov", &mut paints.hov),
+                    ] {
+                        ui.horizontal(|ui| edit_paint(ui, (name, state), paint, palette));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn frame_editor(&mut self, ui: &mut egui::Ui) {
+        let texicon = &mut self.texicon;
+        egui::Grid::new("playground_frame")
+            .num_columns(2)
+            .show(ui, |ui| {
+                optional(
+                    ui,
+                    "Frame size",
+                    &mut texicon.frame_size,
+                    DEFAULT_FRAME,
+                    |ui, size| {
+                        edit_size(ui, size, MAX_FRAME_SIZE);
+                    },
+                );
+
+                ui.label("Frame width");
+                ui.add(egui::Slider::new(
+                    &mut texicon.frame_width,
+                    0.0..=MAX_FRAME_WIDTH,
+                ));
+                ui.end_row();
+
+                optional(ui, "Corner radius", &mut texicon.radius, 8, |ui, radius| {
+                    ui.add(egui::Slider::new(radius, 0..=MAX_RADIUS));
+                });
+                optional(
+                    ui,
+                    "Sense",
+                    &mut texicon.sense,
+                    SenseArea::Frame,
+                    |ui, sense| {
+                        ui.radio_value(sense, SenseArea::Frame, "Frame");
+                        ui.radio_value(sense, SenseArea::ImageAndText, "Image and text");
+                    },
+                );
+            });
+    }
+
+    fn tooltip_editor(&mut self, ui: &mut egui::Ui) {
+        let texicon = &mut self.texicon;
+        egui::Grid::new("playground_tooltip")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Tooltip");
+                ui.text_edit_singleline(&mut texicon.tooltip);
+                ui.end_row();
+
+                ui.label("Tooltip gap");
+                ui.add(egui::Slider::new(
+                    &mut texicon.tooltip_gap,
+                    0.0..=MAX_TOOLTIP_GAP,
+                ));
+                ui.end_row();
+
+                ui.label("Tooltip position");
+                let selected = texicon
+                    .tooltip_position
+                    .and_then(tooltip_position_name)
+                    .unwrap_or("Default");
+                egui::ComboBox::from_id_salt("playground_tooltip_position")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut texicon.tooltip_position, None, "Default");
+                        for (name, align) in TOOLTIP_POSITIONS {
+                            ui.selectable_value(&mut texicon.tooltip_position, Some(align), name);
+                        }
+                    });
+                ui.end_row();
+            });
+    }
+
+    // Copy the texicon as a Rust builder chain, RON or JSON, and load RON or JSON back
+    fn export_editor(&mut self, ui: &mut egui::Ui, palette: &Palette, icons: &IconRegistry) {
+        ui.horizontal(|ui| {
+            let exported = if ui.button("Copy as Rust").clicked() {
+                Some((
+                    texi_export::to_rust(&self.texicon, palette, icons),
+                    "texicon.rs",
+                ))
+            } else if ui.button("Copy as RON").clicked() {
+                Some((texi_export::to_ron(&self.texicon), "texicon.ron"))
+            } else if ui.button("Copy as JSON").clicked() {
+                Some((texi_export::to_json(&self.texicon), "texicon.json"))
+            } else {
+                None
+            };
+            if let Some((text, file_name)) = exported {
+                self.status = Some(export(ui.ctx(), text, file_name));
+            }
+        });
+
+        ui.add(
+            egui::TextEdit::multiline(&mut self.import)
+                .code_editor()
+                .desired_rows(4)
+                .hint_text("Paste RON or JSON here"),
+        );
+        ui.horizontal(|ui| {
+            if ui.button("Load").clicked() {
+                self.status = Some(match texi_export::parse(&self.import) {
+                    Ok(texicon) => {
+                        self.texicon = texicon;
+                        "Loaded the texicon".to_owned()
+                    }
+                    Err(err) => err,
+                });
+            }
+            if let Some(status) = &self.status {
+                ui.label(status);
+            }
+        });
+    }
+}
+
+impl Page for PlaygroundPage {
+    fn id(&self) -> PageId {
+        PageId::Playground
+    }
+
+    fn title(&self) -> &str {
+        "Texicon playground"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        let palette = Palette::current(ui.ctx());
+        self.preview(ui, &palette, cx.icons);
+        ui.weak("Drop SVG or PNG files on the app to try them as icons.");
+        self.state_editor(ui, &palette);
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .id_salt("playground")
+            .show(ui, |ui| {
+                egui::CollapsingHeader::new("Image and text")
+                    .default_open(true)
+                    .show(ui, |ui| self.content_editor(ui, cx));
+                egui::CollapsingHeader::new("Colours")
+                    .default_open(true)
+                    .show(ui, |ui| self.colour_editor(ui, &palette));
+                egui::CollapsingHeader::new("Frame and sense")
+                    .default_open(true)
+                    .show(ui, |ui| self.frame_editor(ui));
+                egui::CollapsingHeader::new("Tooltip")
+                    .default_open(true)
+                    .show(ui, |ui| self.tooltip_editor(ui));
+                egui::CollapsingHeader::new("Export")
+                    .default_open(true)
+                    .show(ui, |ui| self.export_editor(ui, &palette, cx.icons));
+            });
+    }
+}
+
+// === Editors ===
+// A grid row for an option that may be left to the texicon's default.
+// Ticking it starts from `default`.
+fn optional<T: Copy>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut Option<T>,
+    default: T,
+    edit: impl FnOnce(&mut egui::Ui, &mut T),
+) {
+    let mut set = value.is_some();
+    ui.checkbox(&mut set, label);
+    if set {
+        ui.horizontal(|ui| edit(ui, value.get_or_insert(default)));
+    } else {
+        *value = None;
+        ui.weak("texicon default");
+    }
+    ui.end_row();
+}
+
+fn edit_size(ui: &mut egui::Ui, size: &mut Vec2, max: f32) {
+    ui.add(
+        egui::DragValue::new(&mut size.x)
+            .range(0.0..=max)
+            .prefix("w "),
+    );
+    ui.add(
+        egui::DragValue::new(&mut size.y)
+            .range(0.0..=max)
+            .prefix("h "),
+    );
+}
+
+// Put the icon called `name` on the selected texicons of a menu
+fn use_in_menu(ui: &mut egui::Ui, cx: &mut PageContext<'_>, name: &str) {
+    let Some(img) = cx.icons.icon(name) else {
+        return;
+    };
+    ui.menu_button("Use in a menu", |ui| {
+        for menu in [&mut *cx.top_menu, &mut *cx.side_menu, &mut *cx.central_menu] {
+            let selected: Vec<usize> = menu.selected_indices().collect();
+            let button = egui::Button::new(format!("{}: selected texicons", menu.name()));
+            if ui.add_enabled(!selected.is_empty(), button).clicked() {
+                for idx in selected {
+                    menu.set_image(idx, img.clone());
+                }
+                ui.close();
+            }
+        }
+    });
+}
+
+// A colour button, plus a list of the palette roles as shortcuts
+fn edit_paint(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    paint: &mut Paint,
+    palette: &Palette,
+) {
+    let mut color = paint.color(palette);
+    if egui::color_picker::color_edit_button_srgba(
+        ui,
+        &mut color,
+        egui::color_picker::Alpha::OnlyBlend,
+    )
+    .changed()
+    {
+        *paint = Paint::Custom(color);
+    }
+
+    let selected = match paint {
+        Paint::Role(role) => role.name(),
+        Paint::Custom(_) => "custom",
+    };
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(selected)
+        .width(ROLE_COMBO_WIDTH)
+        .show_ui(ui, |ui| {
+            for role in Role::ALL {
+                ui.selectable_value(paint, Paint::Role(role), role.name());
+            }
+        });
+}
@@ -0,0 +1,84 @@
+use crate::animation::{AnimationSettings, Easing, MAX_DURATION};
+use crate::pages::{Page, PageContext, PageId};
+use crate::texi_menu::SelectionMode;
+
+// --------
+// Settings
+// --------
+pub struct SettingsPage;
+
+impl Page for SettingsPage {
+    fn id(&self) -> PageId {
+        PageId::Settings
+    }
+
+    fn title(&self) -> &str {
+        "Settings"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        // Pick how the central menu selects, to try out the selection modes
+        ui.horizontal(|ui| {
+            ui.label("Central menu selection:");
+            let mut mode = cx.central_menu.selection_mode();
+            for (value, text) in [
+                (SelectionMode::Single, "Single"),
+                (SelectionMode::SingleWithDeselect, "Single with deselect"),
+                (SelectionMode::MultipleToggle, "Multiple (Ctrl/Shift-click)"),
+                (SelectionMode::None, "None"),
+            ] {
+                ui.radio_value(&mut mode, value, text);
+            }
+            if mode != cx.central_menu.selection_mode() {
+                cx.central_menu.set_selection_mode(mode);
+            }
+        });
+        animations(ui);
+        menu_layout(ui, cx);
+    }
+}
+
+// The texicons can be dragged to reorder the menus and pinned from one menu to another.
+// This puts them back as loaded.
+fn menu_layout(ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+    ui.horizontal(|ui| {
+        ui.label("Menu layout:");
+        ui.label("Drag texicons along the top and side menus, or from one menu to another.");
+        if ui.button("Reset").clicked() {
+            cx.top_menu.set_items(cx.loaded_menus.top.clone());
+            cx.side_menu.set_items(cx.loaded_menus.side.clone());
+            cx.central_menu.set_items(cx.loaded_menus.central.clone());
+            cx.top_menu.set_selected_texicon(0);
+            cx.central_menu.set_selected_texicon(0);
+            if let Some(idx) = cx.side_menu.position_of(PageId::Settings.action()) {
+                cx.side_menu.set_selected_texicon(idx);
+            }
+        }
+    });
+}
+
+// How the menus animate hover, selection and theme switches
+fn animations(ui: &mut egui::Ui) {
+    let mut settings = AnimationSettings::get(ui.ctx());
+    ui.horizontal(|ui| {
+        ui.label("Animations:");
+        ui.checkbox(&mut settings.reduce_motion, "Reduce motion");
+        ui.add_enabled_ui(!settings.reduce_motion, |ui| {
+            ui.add(
+                egui::Slider::new(&mut settings.duration, 0.0..=MAX_DURATION)
+                    .text("seconds")
+                    .fixed_decimals(2),
+            );
+            egui::ComboBox::from_id_salt("animation_easing")
+                .selected_text(settings.easing.name())
+                .show_ui(ui, |ui| {
+                    for easing in Easing::ALL {
+                        ui.selectable_value(&mut settings.easing, easing, easing.name());
+                    }
+                });
+        });
+    });
+    if settings != AnimationSettings::get(ui.ctx()) {
+        settings.set(ui.ctx());
+    }
+}
@@ -0,0 +1,166 @@
+use egui::color_picker::{color_edit_button_srgba, Alpha};
+
+use crate::custom_theme::{self, CustomTheme, FILE_NAME};
+use crate::export;
+use crate::pages::{Page, PageContext, PageId};
+use crate::palette::{Palette, Role};
+
+// ------------
+// Theme editor
+// ------------
+// Make a palette of your own. It can be added to the theme selector, and
+// while it is the current theme every edit shows on all the menus right away.
+#[derive(Default)]
+pub struct ThemeEditorPage {
+    // Started from the current theme when the page is first shown
+    theme: Option<CustomTheme>,
+    import: String,
+    status: Option<String>,
+}
+
+impl ThemeEditorPage {
+    fn theme_editor(ui: &mut egui::Ui, theme: &mut CustomTheme) {
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut theme.name);
+            ui.checkbox(&mut theme.dark, "Dark")
+                .on_hover_text("Build the other widgets on egui's dark look");
+            if ui.button("Start from the current theme").clicked() {
+                *theme = from_current(ui.ctx(), &theme.name);
+            }
+        });
+
+        egui::Grid::new("theme_editor_colours")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for role in Role::ALL {
+                    ui.label(role.name());
+                    let mut color = theme.palette.get(role);
+                    if color_edit_button_srgba(ui, &mut color, Alpha::Opaque).changed() {
+                        theme.palette.set(role, color);
+                    }
+                    let [r, g, b, _] = color.to_array();
+                    ui.monospace(format!("#{r:02x}{g:02x}{b:02x}"));
+                    ui.end_row();
+                }
+            });
+    }
+
+    // Add the theme to the theme selector and make it current
+    fn selector(ui: &mut egui::Ui, theme: &CustomTheme) {
+        let ctx = ui.ctx().clone();
+        let registered = custom_theme::registered(&ctx);
+        // Once added, the entry follows every edit
+        if registered
+            .as_ref()
+            .is_some_and(|registered| registered != theme)
+        {
+            custom_theme::register(&ctx, theme.clone());
+        }
+        ui.horizontal(|ui| {
+            let mut active = custom_theme::is_active(&ctx);
+            if ui.checkbox(&mut active, "Use this theme").changed() {
+                custom_theme::register(&ctx, theme.clone());
+                custom_theme::set_active(&ctx, active);
+            }
+            if registered.is_none() && ui.button("Add to the theme selector").clicked() {
+                custom_theme::register(&ctx, theme.clone());
+            }
+        });
+    }
+
+    // Save the theme to a file and load one back, or copy and paste it as RON
+    fn file_editor(&mut self, ui: &mut egui::Ui) {
+        let Some(theme) = &mut self.theme else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                self.status = Some(export::export(ui.ctx(), theme.to_ron(), FILE_NAME));
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui.button(format!("Load {FILE_NAME}")).clicked() {
+                let loaded = export::path(FILE_NAME)
+                    .ok_or_else(|| "There is no storage directory".to_owned())
+                    .and_then(|path| {
+                        std::fs::read_to_string(&path)
+                            .map_err(|err| format!("Can't read {}: {err}", path.display()))
+                    })
+                    .and_then(|text| CustomTheme::parse(&text));
+                self.status = Some(match loaded {
+                    Ok(loaded) => {
+                        *theme = loaded;
+                        format!("Loaded {FILE_NAME}")
+                    }
+                    Err(err) => err,
+                });
+            }
+        });
+        ui.add(
+            egui::TextEdit::multiline(&mut self.import)
+                .code_editor()
+                .desired_rows(4)
+                .hint_text("Paste a theme here"),
+        );
+        ui.horizontal(|ui| {
+            if ui.button("Load").clicked() {
+                self.status = Some(match CustomTheme::parse(&self.import) {
+                    Ok(loaded) => {
+                        *theme = loaded;
+                        "Loaded the theme".to_owned()
+                    }
+                    Err(err) => err,
+                });
+            }
+            if let Some(status) = &self.status {
+                ui.label(status);
+            }
+        });
+    }
+}
+
+impl Page for ThemeEditorPage {
+    fn id(&self) -> PageId {
+        PageId::ThemeEditor
+    }
+
+    fn title(&self) -> &str {
+        "Theme editor"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        let theme = self.theme.get_or_insert_with(|| {
+            custom_theme::registered(ui.ctx()).unwrap_or_else(|| from_current(ui.ctx(), "Custom"))
+        });
+        Self::selector(ui, theme);
+        ui.separator();
+
+        ui.horizontal_top(|ui| {
+            ui.vertical(|ui| {
+                egui::ScrollArea::vertical()
+                    .id_salt("theme_editor")
+                    .show(ui, |ui| {
+                        if let Some(theme) = &mut self.theme {
+                            Self::theme_editor(ui, theme);
+                        }
+                        ui.separator();
+                        self.file_editor(ui);
+                    });
+            });
+            // The top and side menus are on screen already
+            ui.vertical(|ui| {
+                ui.weak("The menus show the theme while it's in use.");
+                cx.events.extend(cx.central_menu.draw_texicons(ui));
+            });
+        });
+    }
+}
+
+// A copy of the current theme's palette, to edit
+fn from_current(ctx: &egui::Context, name: &str) -> CustomTheme {
+    let palette = Palette::current(ctx);
+    let dark =
+        egui::Rgba::from(palette.text).intensity() > egui::Rgba::from(palette.base).intensity();
+    CustomTheme::new(name, dark, palette)
+}
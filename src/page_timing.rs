@@ -0,0 +1,148 @@
+use egui::FontId;
+
+use crate::badge_demo::BadgeDemo;
+use crate::bench;
+use crate::bench_plot::BenchPlot;
+use crate::export::export;
+use crate::pages::{Page, PageContext, PageId};
+use crate::stress::StressScene;
+use crate::texi_menu::TexiMenu;
+
+// ------------
+// Timing stuff
+// ------------
+// Benchmarks of every menu, their plots and export, the stress test,
+// and a badge that counts up on this page's side menu texicon
+#[derive(Default)]
+pub struct TimingPage {
+    bench_plot: BenchPlot,
+    stress: StressScene,
+    badges: BadgeDemo,
+    export_status: Option<String>,
+}
+
+impl TimingPage {
+    // Start with the stress test showing `count` texicons
+    pub fn with_stress(count: usize) -> Self {
+        let mut stress = StressScene::new(count);
+        stress.enabled = true;
+        Self {
+            stress,
+            ..Self::default()
+        }
+    }
+
+    // The menus with timings, plus the stress test when it is on
+    fn benchmarked_menus<'a>(&'a self, cx: &'a PageContext<'_>) -> Vec<&'a TexiMenu> {
+        let mut menus: Vec<&TexiMenu> = vec![&*cx.top_menu, &*cx.side_menu, &*cx.central_menu];
+        if self.stress.enabled {
+            menus.push(self.stress.menu());
+        }
+        menus
+    }
+
+    fn reset_benchmarks(&mut self, cx: &mut PageContext<'_>) {
+        for menu in [&mut *cx.top_menu, &mut *cx.side_menu, &mut *cx.central_menu] {
+            menu.frame_times_mut().clear();
+        }
+        self.stress.menu_mut().frame_times_mut().clear();
+        self.export_status = None;
+    }
+
+    // Export and reset buttons for the benchmarks.
+    // Exports are copied to the clipboard, and also saved to a file when running natively.
+    fn benchmark_controls(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        ui.horizontal(|ui| {
+            let csv = ui.button("Export CSV").clicked();
+            let json = ui.button("Export JSON").clicked();
+            if ui.button("Reset").clicked() {
+                self.reset_benchmarks(cx);
+            }
+            if csv || json {
+                let named: Vec<(&str, &bench::FrameTimes)> = self
+                    .benchmarked_menus(cx)
+                    .into_iter()
+                    .map(|menu| (menu.name(), menu.frame_times()))
+                    .collect();
+                let (text, file_name) = if csv {
+                    (bench::to_csv(&named), "texicon_benchmarks.csv")
+                } else {
+                    (bench::to_json(&named), "texicon_benchmarks.json")
+                };
+                self.export_status = Some(export(ui.ctx(), text, file_name));
+            }
+            if let Some(status) = &self.export_status {
+                ui.label(status);
+            }
+        });
+    }
+
+    // Collapsible line chart and histogram of the recent frame times
+    fn benchmark_plot(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        // The plot is taken out while drawing, as `named` borrows the menus
+        let mut plot = std::mem::take(&mut self.bench_plot);
+        let menus = self.benchmarked_menus(cx);
+        let named: Vec<(&str, &bench::FrameTimes)> = menus
+            .iter()
+            .map(|menu| (menu.name(), menu.frame_times()))
+            .collect();
+        let reset = plot.show(ui, &named);
+        self.bench_plot = plot;
+        if reset {
+            self.reset_benchmarks(cx);
+        }
+    }
+}
+
+impl Page for TimingPage {
+    fn id(&self) -> PageId {
+        PageId::Timing
+    }
+
+    fn title(&self) -> &str {
+        "Timing benchmarks (for each group)"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        crate::app::print_build_info(ui);
+        print_benchmarks(ui, &self.benchmarked_menus(cx));
+        self.benchmark_controls(ui, cx);
+        self.benchmark_plot(ui, cx);
+        self.badges.tick(ui.ctx());
+        self.badges.controls(ui);
+        self.badges.apply(cx.side_menu);
+        self.stress.controls(ui);
+        if self.stress.enabled {
+            self.stress.show(ui);
+        } else {
+            cx.events.extend(cx.central_menu.draw_texicons(ui));
+        }
+    }
+}
+
+fn print_benchmarks(ui: &mut egui::Ui, menus: &[&TexiMenu]) {
+    for menu in menus {
+        let times = menu.frame_times();
+        let s = match times.stats() {
+            Some(stats) => format!(
+                "> {:<13} Count: {},  Average: {:.1} us  |  Last {}: min {:.1}  p50 {:.1}  p95 {:.1}  p99 {:.1}  max {:.1} us",
+                menu.name(),
+                times.total_count(),
+                times.total_mean(),
+                stats.frames,
+                stats.min,
+                stats.p50,
+                stats.p95,
+                stats.p99,
+                stats.max
+            ),
+            None => format!("> {:<13} No frames yet", menu.name()),
+        };
+        ui.add(egui::Label::new(
+            egui::RichText::new(s)
+                .color(ui.visuals().strong_text_color())
+                .font(FontId::new(16., egui::FontFamily::Monospace)),
+        ));
+        ui.add_space(4.);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::action::{Action, TexiEvent};
+use crate::icon_registry::IconRegistry;
+use crate::menu_file::Menus;
+use crate::texi_menu::TexiMenu;
+
+// === Page ids ===
+// One per side menu entry. Saved with the app state and used in the URL hash on the web.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum PageId {
+    #[default]
+    Experiments,
+    Timing,
+    Filtering,
+    Playground,
+    Gallery,
+    ThemeEditor,
+    Settings,
+}
+
+impl PageId {
+    pub const ALL: [Self; 7] = [
+        Self::Experiments,
+        Self::Timing,
+        Self::Filtering,
+        Self::Playground,
+        Self::Gallery,
+        Self::ThemeEditor,
+        Self::Settings,
+    ];
+
+    // The side menu action that leads to this page
+    pub fn action(self) -> Action {
+        match self {
+            Self::Experiments => Action::Experiments,
+            Self::Timing => Action::Timing,
+            Self::Filtering => Action::Filtering,
+            Self::Playground => Action::Playground,
+            Self::Gallery => Action::Gallery,
+            Self::ThemeEditor => Action::ThemeEditor,
+            Self::Settings => Action::Settings,
+        }
+    }
+
+    pub fn from_action(action: Action) -> Option<Self> {
+        Self::ALL.into_iter().find(|page| page.action() == action)
+    }
+
+    // The URL hash, without the `#`
+    pub fn slug(self) -> &'static str {
+        match self {
+            Self::Experiments => "experiments",
+            Self::Timing => "timing",
+            Self::Filtering => "filtering",
+            Self::Playground => "playground",
+            Self::Gallery => "gallery",
+            Self::ThemeEditor => "theme",
+            Self::Settings => "settings",
+        }
+    }
+
+    // Accepts the hash with or without its `#`
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        let slug = slug.trim_start_matches('#');
+        Self::ALL
+            .into_iter()
+            .find(|page| page.slug().eq_ignore_ascii_case(slug))
+    }
+}
+
+// -----
+// Pages
+// -----
+// What a page may use besides its own state
+pub struct PageContext<'a> {
+    pub top_menu: &'a mut TexiMenu,
+    pub side_menu: &'a mut TexiMenu,
+    pub central_menu: &'a mut TexiMenu,
+    // The menus as loaded, before any reordering
+    pub loaded_menus: &'a Menus,
+    pub last_event: Option<&'a str>,
+    // Every icon the app knows: built-in, from the icon directory and dropped on the app
+    pub icons: &'a IconRegistry,
+    // Events of the central menu, if the page drew it. The app handles them afterwards.
+    pub events: Vec<TexiEvent>,
+}
+
+// The content of the central panel for one side menu entry
+pub trait Page {
+    fn id(&self) -> PageId;
+    fn title(&self) -> &str;
+    fn ui(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>);
+}
+
+// ------
+// Router
+// ------
+pub struct Router {
+    pages: Vec<Box<dyn Page>>,
+    current: PageId,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self {
+            pages: vec![
+                Box::new(crate::page_experiments::ExperimentsPage),
+                Box::new(crate::page_timing::TimingPage::default()),
+                Box::new(crate::page_filtering::FilteringPage::default()),
+                Box::new(crate::page_playground::PlaygroundPage::default()),
+                Box::new(crate::page_gallery::GalleryPage::default()),
+                Box::new(crate::page_theme_editor::ThemeEditorPage::default()),
+                Box::new(crate::page_settings::SettingsPage),
+            ],
+            current: PageId::default(),
+        }
+    }
+}
+
+impl Router {
+    pub fn current(&self) -> PageId {
+        self.current
+    }
+
+    pub fn navigate(&mut self, page: PageId) {
+        self.current = page;
+        set_url_hash(page);
+    }
+
+    // Go back to a saved page, leaving the URL hash alone so a deep link can still win
+    pub fn restore(&mut self, page: PageId) {
+        self.current = page;
+    }
+
+    // Swap in a page, e.g. one set up from the command line
+    pub fn replace(&mut self, page: Box<dyn Page>) {
+        if let Some(slot) = self.pages.iter_mut().find(|slot| slot.id() == page.id()) {
+            *slot = page;
+        }
+    }
+
+    // Follow the URL hash (a deep link, or the browser's back button).
+    // Returns true if that changed the page.
+    #[cfg(target_arch = "wasm32")]
+    pub fn follow_url_hash(&mut self) -> bool {
+        match url_hash().as_deref().and_then(PageId::from_slug) {
+            Some(page) if page != self.current => {
+                self.current = page;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, cx: &mut PageContext<'_>) {
+        let Some(page) = self.pages.iter_mut().find(|page| page.id() == self.current) else {
+            return;
+        };
+        crate::app::print_heading(ui, page.title());
+        page.ui(ui, cx);
+    }
+}
+
+// === URL hash (web only) ===
+#[cfg(target_arch = "wasm32")]
+fn url_hash() -> Option<String> {
+    web_sys::window()?.location().hash().ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn set_url_hash(page: PageId) {
+    if let Some(window) = web_sys::window() {
+        window.location().set_hash(page.slug()).ok();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn set_url_hash(_page: PageId) {}
@@ -0,0 +1,195 @@
+use egui::Color32;
+
+// === Palette ===
+// The colours the texicon menus are styled with, copied from the current
+// themenator palette so menu styles don't depend on the themenator types.
+#[rustfmt::skip]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub base:     Color32,
+    pub mantle:   Color32,
+    pub crust:    Color32,
+    pub surface0: Color32,
+    pub surface2: Color32,
+    pub overlay0: Color32,
+    pub text:     Color32,
+    pub subtext0: Color32,
+    pub mauve:    Color32,
+    pub teal:     Color32,
+    pub green:    Color32,
+    pub red:      Color32,
+    pub yellow:   Color32,
+    pub blue:     Color32,
+}
+
+impl Palette {
+    // Get the palette of the current theme (cheap).
+    // That's the custom theme's palette while it is the current theme.
+    pub fn current(ctx: &egui::Context) -> Self {
+        if let Some(palette) = crate::custom_theme::active_palette(ctx) {
+            return palette;
+        }
+        let palette = egui_widget_themenator::ThemeVariant::get_current_palette(ctx);
+        Self {
+            base: palette.base,
+            mantle: palette.mantle,
+            crust: palette.crust,
+            surface0: palette.surface0,
+            surface2: palette.surface2,
+            overlay0: palette.overlay0,
+            text: palette.text,
+            subtext0: palette.subtext0,
+            mauve: palette.mauve,
+            teal: palette.teal,
+            green: palette.green,
+            red: palette.red,
+            yellow: palette.yellow,
+            blue: palette.blue,
+        }
+    }
+
+    pub fn get(&self, role: Role) -> Color32 {
+        match role {
+            Role::Base => self.base,
+            Role::Mantle => self.mantle,
+            Role::Crust => self.crust,
+            Role::Surface0 => self.surface0,
+            Role::Surface2 => self.surface2,
+            Role::Overlay0 => self.overlay0,
+            Role::Text => self.text,
+            Role::Subtext0 => self.subtext0,
+            Role::Mauve => self.mauve,
+            Role::Teal => self.teal,
+            Role::Green => self.green,
+            Role::Red => self.red,
+            Role::Yellow => self.yellow,
+            Role::Blue => self.blue,
+        }
+    }
+
+    pub fn set(&mut self, role: Role, color: Color32) {
+        let slot = match role {
+            Role::Base => &mut self.base,
+            Role::Mantle => &mut self.mantle,
+            Role::Crust => &mut self.crust,
+            Role::Surface0 => &mut self.surface0,
+            Role::Surface2 => &mut self.surface2,
+            Role::Overlay0 => &mut self.overlay0,
+            Role::Text => &mut self.text,
+            Role::Subtext0 => &mut self.subtext0,
+            Role::Mauve => &mut self.mauve,
+            Role::Teal => &mut self.teal,
+            Role::Green => &mut self.green,
+            Role::Red => &mut self.red,
+            Role::Yellow => &mut self.yellow,
+            Role::Blue => &mut self.blue,
+        };
+        *slot = color;
+    }
+
+    pub fn shade(&self, shade: Shade) -> Color32 {
+        let color = self.get(shade.role);
+        if shade.factor == 1.0 {
+            color
+        } else {
+            color.gamma_multiply(shade.factor)
+        }
+    }
+}
+
+// === Colour roles ===
+// A palette entry, looked up in whatever theme is current when drawing
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+pub enum Role {
+    Base,
+    Mantle,
+    Crust,
+    Surface0,
+    Surface2,
+    Overlay0,
+    Text,
+    Subtext0,
+    Mauve,
+    Teal,
+    Green,
+    Red,
+    Yellow,
+    Blue,
+}
+
+impl Role {
+    pub const ALL: [Self; 14] = [
+        Self::Base,
+        Self::Mantle,
+        Self::Crust,
+        Self::Surface0,
+        Self::Surface2,
+        Self::Overlay0,
+        Self::Text,
+        Self::Subtext0,
+        Self::Mauve,
+        Self::Teal,
+        Self::Green,
+        Self::Red,
+        Self::Yellow,
+        Self::Blue,
+    ];
+
+    // Semantic names for the roles the menu styles are built from
+    pub const BACKGROUND: Self = Self::Base;
+    pub const SURFACE: Self = Self::Surface0;
+    pub const ACCENT: Self = Self::Mauve;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Base => "base",
+            Self::Mantle => "mantle",
+            Self::Crust => "crust",
+            Self::Surface0 => "surface0",
+            Self::Surface2 => "surface2",
+            Self::Overlay0 => "overlay0",
+            Self::Text => "text",
+            Self::Subtext0 => "subtext0",
+            Self::Mauve => "mauve",
+            Self::Teal => "teal",
+            Self::Green => "green",
+            Self::Red => "red",
+            Self::Yellow => "yellow",
+            Self::Blue => "blue",
+        }
+    }
+
+    pub const fn full(self) -> Shade {
+        Shade {
+            role: self,
+            factor: 1.0,
+        }
+    }
+
+    // Half strength, e.g. for unselected text
+    pub const fn dim(self) -> Shade {
+        Shade {
+            role: self,
+            factor: 0.5,
+        }
+    }
+
+    // Mostly transparent, `gamma` out of 255
+    pub const fn faint(self, gamma: u8) -> Shade {
+        Shade {
+            role: self,
+            factor: gamma as f32 / 255.0,
+        }
+    }
+}
+
+// A palette role, optionally faded with `Color32::gamma_multiply`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shade {
+    pub role: Role,
+    pub factor: f32,
+}
+
+pub const DIM_TEXT: Shade = Role::Text.dim();
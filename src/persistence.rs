@@ -0,0 +1,91 @@
+use egui_widget_themenator::ThemeVariant;
+
+use crate::animation::AnimationSettings;
+use crate::auto_theme::AutoTheme;
+use crate::custom_theme::CustomTheme;
+use crate::menu_layout::MenuLayout;
+use crate::pages::PageId;
+
+// Bump this whenever `PersistedState` changes shape or meaning.
+// Saved state with a different version is ignored and the defaults are used.
+const STATE_VERSION: u32 = 6;
+
+// === Theme ===
+// Our own copy of the theme variants so the saved format doesn't depend on
+// how the themenator crate chooses to (de)serialize its types.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum SavedTheme {
+    Latte,
+    Frappe,
+    Macchiato,
+    #[default]
+    Mocha,
+}
+
+impl SavedTheme {
+    pub fn from_variant(variant: ThemeVariant) -> Self {
+        match variant {
+            ThemeVariant::Latte => Self::Latte,
+            ThemeVariant::Frappe => Self::Frappe,
+            ThemeVariant::Macchiato => Self::Macchiato,
+            ThemeVariant::Mocha => Self::Mocha,
+        }
+    }
+
+    pub fn to_variant(self) -> ThemeVariant {
+        match self {
+            Self::Latte => ThemeVariant::Latte,
+            Self::Frappe => ThemeVariant::Frappe,
+            Self::Macchiato => ThemeVariant::Macchiato,
+            Self::Mocha => ThemeVariant::Mocha,
+        }
+    }
+}
+
+// === Persisted state ===
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct PersistedState {
+    version:                u32,
+    pub top_selected:       usize,
+    pub side_selected:      usize,
+    pub central_selected:   usize,
+    pub theme:              SavedTheme,
+    pub auto_theme:         AutoTheme,
+    pub page:               PageId,
+    // The theme editor's theme, if it was added to the theme selector
+    pub custom_theme:       Option<CustomTheme>,
+    pub custom_active:      bool,
+    pub animation:          AnimationSettings,
+    // The order of the texicons after dragging them around
+    pub menu_layout:        Option<MenuLayout>,
+}
+
+impl PersistedState {
+    pub fn new() -> Self {
+        Self {
+            version: STATE_VERSION,
+            ..Default::default()
+        }
+    }
+
+    // Load the saved state, if there is any and it was written by a compatible version.
+    // A missing key, a malformed value or an old schema all end up as `None`.
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Option<Self> {
+        let state: Self = eframe::get_value(storage?, eframe::APP_KEY)?;
+        if state.version == STATE_VERSION {
+            Some(state)
+        } else {
+            log::info!(
+                "Ignoring saved app state with version {} (expected {})",
+                state.version,
+                STATE_VERSION
+            );
+            None
+        }
+    }
+
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, self);
+    }
+}
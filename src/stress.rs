@@ -0,0 +1,273 @@
+use egui::vec2;
+
+use crate::bench::{self, FrameTimes, Stopwatch};
+use crate::menu_file::BUILTIN_ICONS;
+use crate::texi_menu::{ItemStyle, MyTexicon, Overflow, TexiLayout, TexiMenu};
+use crate::texi_style::TexiStyle;
+
+// === Constants ===
+#[rustfmt::skip] pub const MIN_TEXICONS: usize     = 100;
+#[rustfmt::skip] pub const MAX_TEXICONS: usize     = 10_000;
+#[rustfmt::skip] pub const DEFAULT_TEXICONS: usize = 1000;
+#[rustfmt::skip] const DEFAULT_SEED: u64           = 0x5EED_7E41_C0DE;
+#[rustfmt::skip] const TEXI_WIDTH: f32             = 64.0;
+#[rustfmt::skip] const TEXI_HEIGHT: f32            = 72.0;
+#[rustfmt::skip] const TEXI_GAP: f32               = 8.0;
+#[rustfmt::skip] const STYLE_SCALE: f32            = 0.6;
+// One in this many texicons is disabled
+#[rustfmt::skip] const DISABLED_ONE_IN: u64        = 8;
+#[rustfmt::skip] const MAX_WORDS: u64              = 4;
+
+#[rustfmt::skip]
+static WORDS: &[&str] = &[
+    "Flux", "Sample", "Spectrum", "Calibration", "Run", "Baseline", "Centrifuge",
+    "Incubator", "Log", "Ratio", "Thermocouple", "Gain", "Offset", "Buffer",
+];
+
+// -------------
+// Random source
+// -------------
+// xorshift64*: tiny, seedable, and the same on every platform, so a scene is reproducible
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // In `0..n`
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n.max(1)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        items.get(self.below(items.len() as u64) as usize)
+    }
+}
+
+// -----------
+// Stress test
+// -----------
+// A wrapping grid of many texicons with random icons, text lengths, styles and states
+pub struct StressScene {
+    pub enabled: bool,
+    count: usize,
+    seed: u64,
+    menu: TexiMenu,
+}
+
+impl Default for StressScene {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEXICONS)
+    }
+}
+
+impl StressScene {
+    pub fn new(count: usize) -> Self {
+        Self {
+            enabled: false,
+            count,
+            seed: DEFAULT_SEED,
+            menu: build_menu(count, DEFAULT_SEED),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn menu(&self) -> &TexiMenu {
+        &self.menu
+    }
+    pub fn menu_mut(&mut self) -> &mut TexiMenu {
+        &mut self.menu
+    }
+
+    fn regenerate(&mut self) {
+        self.menu = build_menu(self.count, self.seed);
+    }
+
+    // On/off, how many texicons, and a new random scene
+    pub fn controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.enabled, "Stress test");
+            let slider = egui::Slider::new(&mut self.count, MIN_TEXICONS..=MAX_TEXICONS)
+                .logarithmic(true)
+                .text("texicons");
+            if ui.add_enabled(self.enabled, slider).changed() {
+                self.regenerate();
+            }
+            if ui
+                .add_enabled(self.enabled, egui::Button::new("Shuffle"))
+                .clicked()
+            {
+                self.seed = Rng::new(self.seed).next();
+                self.regenerate();
+            }
+        });
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical()
+            .id_salt("stress_scene")
+            .auto_shrink(false)
+            .show(ui, |ui| {
+                let width = ui.available_width();
+                let size = vec2(width, self.menu.wrapped_height(width));
+                ui.allocate_ui(size, |ui| self.menu.draw_texicons(ui));
+            });
+    }
+}
+
+fn build_menu(count: usize, seed: u64) -> TexiMenu {
+    let mut rng = Rng::new(seed);
+
+    // The central menu presets, shrunk to fit the grid
+    let styles: Vec<TexiStyle> = crate::texi_central_menu::PRESETS
+        .iter()
+        .map(|preset| TexiStyle {
+            frame_size: None,
+            ..preset.scaled(STYLE_SCALE)
+        })
+        .collect();
+
+    let mut items = Vec::with_capacity(count);
+    let mut presets = Vec::with_capacity(count);
+    for idx in 0..count {
+        let words: Vec<&str> = (0..rng.below(MAX_WORDS + 1))
+            .filter_map(|_| rng.pick(WORDS).copied())
+            .collect();
+        let Some((_, img)) = rng.pick(&BUILTIN_ICONS) else {
+            continue;
+        };
+        items.push(MyTexicon {
+            img: img.clone(),
+            text: words.join(" "),
+            tooltip: format!("Texicon #{idx}"),
+            enabled: rng.below(DISABLED_ONE_IN) != 0,
+            action: None,
+            style: ItemStyle::default(),
+            badge: None,
+            context: Vec::new(),
+            origin: None,
+        });
+        if let Some(style) = rng.pick(&styles) {
+            presets.push(*style);
+        }
+    }
+    let selected = rng.below(count as u64) as usize;
+
+    let mut menu = TexiMenu::new(
+        items,
+        TexiLayout::Horizontal,
+        vec2(TEXI_WIDTH, TEXI_HEIGHT),
+        TEXI_GAP,
+        presets,
+    )
+    .label("Stress test")
+    .overflow(Overflow::Wrap);
+    menu.set_selected_texicon(selected);
+    menu
+}
+
+// --------
+// Headless
+// --------
+// Timings of a headless run, in microseconds per frame
+pub struct StressReport {
+    pub texicons: usize,
+    // Laying out and painting the texicons (the menu's own timer)
+    pub layout: FrameTimes,
+    // Turning the frame's shapes into meshes
+    pub tessellation: FrameTimes,
+    // Meshes and callbacks in the last frame
+    pub primitives: usize,
+}
+
+impl StressReport {
+    pub fn to_json(&self) -> String {
+        bench::to_json(&[
+            ("Layout", &self.layout),
+            ("Tessellation", &self.tessellation),
+        ])
+    }
+}
+
+// Runs the stress scene for `frames` frames without a window or GPU.
+// Install the image loaders on `ctx` first (`egui_extras::install_image_loaders`).
+pub fn run_headless(
+    ctx: &egui::Context,
+    count: usize,
+    frames: usize,
+    screen_size: egui::Vec2,
+) -> StressReport {
+    let mut scene = StressScene::new(count);
+    let mut tessellation = FrameTimes::default();
+    let mut primitives = 0;
+
+    let raw_input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, screen_size)),
+        ..Default::default()
+    };
+    for _ in 0..frames {
+        let output = ctx.run_ui(raw_input.clone(), |ui| {
+            egui::CentralPanel::default().show(ui, |ui| scene.show(ui));
+        });
+        let stopwatch = Stopwatch::start();
+        primitives = ctx.tessellate(output.shapes, output.pixels_per_point).len();
+        tessellation.push(stopwatch.elapsed_us());
+    }
+
+    StressReport {
+        texicons: count,
+        layout: scene.menu.frame_times().clone(),
+        tessellation,
+        primitives,
+    }
+}
+
+// ----------------------
+// Command line arguments
+// ----------------------
+// `--stress <count>` opens the app with the stress test on.
+// Adding `--headless [--frames <n>]` runs it without a window and prints the timings as JSON.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StressArgs {
+    pub count: Option<usize>,
+    pub headless: bool,
+    pub frames: Option<usize>,
+}
+
+impl StressArgs {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        while let Some(arg) = args.next() {
+            let mut number = |name: &str| {
+                let value = args.next().unwrap_or_default();
+                value
+                    .parse::<usize>()
+                    .map_err(|err| format!("{name} {value:?}: {err}"))
+            };
+            match arg.as_str() {
+                "--stress" => {
+                    let count = number("--stress")?;
+                    parsed.count = Some(count.clamp(MIN_TEXICONS, MAX_TEXICONS));
+                }
+                "--frames" => parsed.frames = Some(number("--frames")?),
+                "--headless" => parsed.headless = true,
+                _ => return Err(format!("unknown argument {arg:?}")),
+            }
+        }
+        if parsed.headless && parsed.count.is_none() {
+            return Err("--headless needs --stress <count>".to_owned());
+        }
+        Ok(parsed)
+    }
+}
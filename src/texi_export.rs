@@ -0,0 +1,280 @@
+use egui::{vec2, Color32};
+
+use crate::icon_registry::IconRegistry;
+use crate::menu_file::{builtin_icon, file_uri};
+use crate::page_playground::{Paint, PlaygroundTexicon, StatePaints};
+use crate::palette::{Palette, Role};
+use crate::texi_style::{tooltip_position_name, SenseArea, TOOLTIP_POSITIONS};
+
+// -------------
+// Rust builders
+// -------------
+// The `Texicon::new(..)` chain that draws the texicon as configured.
+// Colours that match a palette entry refer to the themenator's palette, so the code
+// follows the theme without this app's `Palette`.
+// `icons` tells where non-builtin icons were loaded from.
+pub fn to_rust(texicon: &PlaygroundTexicon, palette: &Palette, icons: &IconRegistry) -> String {
+    let mut calls: Vec<(String, String)> = Vec::new();
+    let mut call = |name: &str, arg: String| calls.push((name.to_owned(), arg));
+
+    // Enabled and not selected are the texicon's defaults
+    if !texicon.enabled {
+        call("enabled", "false".to_owned());
+    }
+    if texicon.selected {
+        call("selected", "true".to_owned());
+    }
+    for (name, paints) in [
+        ("bkgnd_col", texicon.bkgnd_col),
+        ("img_tint_col", texicon.img_tint_col),
+        ("text_col", texicon.text_col),
+        ("frame_col", texicon.frame_col),
+    ] {
+        call(name, color_expr(paints.normal, palette));
+        call(&format!("{name}_sel"), color_expr(paints.sel, palette));
+        call(&format!("{name}_hov"), color_expr(paints.hov, palette));
+    }
+    call("frame_width", format!("{:?}", texicon.frame_width));
+    call("tooltip_gap", format!("{:?}", texicon.tooltip_gap));
+    call("tooltip_text", format!("{:?}", texicon.tooltip));
+    if texicon.show_text {
+        call("text", format!("{:?}", texicon.text));
+    }
+    if let Some(size) = texicon.img_size {
+        call("img_size", format!("vec2({:?}, {:?})", size.x, size.y));
+    }
+    if let Some(scale) = texicon.img_scale_hov {
+        call("img_scale_hov", format!("{scale:?}"));
+    }
+    if let Some(size) = texicon.text_size {
+        call("text_size", format!("{size:?}"));
+    }
+    if let Some(gap) = texicon.img_text_gap {
+        call("img_text_gap", format!("{gap:?}"));
+    }
+    if let Some(sense) = texicon.sense {
+        call("sense", format!("TexiSense::{sense:?}"));
+    }
+    if let Some(size) = texicon.frame_size {
+        call("frame_size", format!("vec2({:?}, {:?})", size.x, size.y));
+    }
+    if let Some(radius) = texicon.radius {
+        call("radius", radius.to_string());
+    }
+    if let Some(name) = texicon.tooltip_position.and_then(tooltip_position_name) {
+        call("tooltip_position", format!("RectAlign::{name}"));
+    }
+
+    let mut rust = String::from(
+        "// With `let palette = egui_widget_themenator::ThemeVariant::get_current_palette(ui.ctx());`\n",
+    );
+    let (image, todo) = image_expr(&texicon.icon, icons);
+    if let Some(todo) = todo {
+        rust.push_str(&format!("// TODO: {todo}\n"));
+    }
+    rust.push_str(&format!("Texicon::new({image})"));
+    for (name, arg) in calls {
+        rust.push_str(&format!("\n    .{name}({arg})"));
+    }
+    rust
+}
+
+// The image of the texicon, and what's left to do for it to load.
+// Built-in icons are embedded with `include_image!`, so reuse their path. Icons from the
+// icon directory load from their file; dropped icons have no file to point at.
+fn image_expr(icon: &str, icons: &IconRegistry) -> (String, Option<String>) {
+    let path = builtin_icon(icon).and_then(|img| {
+        img.uri()
+            .and_then(|uri| uri.strip_prefix("bytes://"))
+            .map(str::to_owned)
+    });
+    if let Some(path) = path {
+        return (format!("include_image!({path:?})"), None);
+    }
+    let (uri, todo) = match icons.entry(icon).and_then(|entry| entry.path.as_ref()) {
+        Some(file) => (file_uri(file), None),
+        None => (
+            format!("file://{icon}"),
+            Some(format!("add {icon:?}, it was dropped on the demo app")),
+        ),
+    };
+    (format!("egui::ImageSource::Uri({uri:?}.into())"), todo)
+}
+
+fn color_expr(paint: Paint, palette: &Palette) -> String {
+    let role = match paint {
+        Paint::Role(role) => Some(role),
+        Paint::Custom(color) => Role::ALL
+            .into_iter()
+            .find(|&role| palette.get(role) == color),
+    };
+    // The roles are named after the themenator palette's fields
+    if let Some(role) = role {
+        return format!("palette.{}", role.name());
+    }
+    let [r, g, b, a] = paint.color(palette).to_array();
+    if a == u8::MAX {
+        format!("Color32::from_rgb({r}, {g}, {b})")
+    } else {
+        format!("Color32::from_rgba_premultiplied({r}, {g}, {b}, {a})")
+    }
+}
+
+// === RON and JSON ===
+// Our own copy of the playground texicon, so the format doesn't depend on how
+// egui chooses to (de)serialize its types. Missing fields take the playground defaults.
+#[rustfmt::skip]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+struct TexiconDef {
+    icon:             String,
+    text:             String,
+    show_text:        bool,
+    tooltip:          String,
+    img_size:         Option<[f32; 2]>,
+    img_scale_hov:    Option<f32>,
+    text_size:        Option<f32>,
+    img_text_gap:     Option<f32>,
+    sense:            Option<SenseArea>,
+    bkgnd_col:        StateDef,
+    img_tint_col:     StateDef,
+    text_col:         StateDef,
+    frame_col:        StateDef,
+    frame_size:       Option<[f32; 2]>,
+    frame_width:      f32,
+    radius:           Option<u8>,
+    tooltip_gap:      f32,
+    // One of the `RectAlign` constants, e.g. "BOTTOM"
+    tooltip_position: Option<String>,
+    enabled:          bool,
+    selected:         bool,
+}
+
+impl Default for TexiconDef {
+    fn default() -> Self {
+        Self::from_texicon(&PlaygroundTexicon::default())
+    }
+}
+
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+struct StateDef {
+    normal: PaintDef,
+    sel: PaintDef,
+    hov: PaintDef,
+}
+
+// A palette role by name, or premultiplied RGBA
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+enum PaintDef {
+    Role(Role),
+    Rgba([u8; 4]),
+}
+
+impl StateDef {
+    fn from_paints(paints: StatePaints) -> Self {
+        let paint = |paint| match paint {
+            Paint::Role(role) => PaintDef::Role(role),
+            Paint::Custom(color) => PaintDef::Rgba(color.to_array()),
+        };
+        Self {
+            normal: paint(paints.normal),
+            sel: paint(paints.sel),
+            hov: paint(paints.hov),
+        }
+    }
+
+    fn to_paints(self) -> StatePaints {
+        let paint = |paint| match paint {
+            PaintDef::Role(role) => Paint::Role(role),
+            PaintDef::Rgba([r, g, b, a]) => {
+                Paint::Custom(Color32::from_rgba_premultiplied(r, g, b, a))
+            }
+        };
+        StatePaints::new(paint(self.normal), paint(self.sel), paint(self.hov))
+    }
+}
+
+impl TexiconDef {
+    fn from_texicon(texicon: &PlaygroundTexicon) -> Self {
+        Self {
+            icon: texicon.icon.clone(),
+            text: texicon.text.clone(),
+            show_text: texicon.show_text,
+            tooltip: texicon.tooltip.clone(),
+            img_size: texicon.img_size.map(|size| [size.x, size.y]),
+            img_scale_hov: texicon.img_scale_hov,
+            text_size: texicon.text_size,
+            img_text_gap: texicon.img_text_gap,
+            sense: texicon.sense,
+            bkgnd_col: StateDef::from_paints(texicon.bkgnd_col),
+            img_tint_col: StateDef::from_paints(texicon.img_tint_col),
+            text_col: StateDef::from_paints(texicon.text_col),
+            frame_col: StateDef::from_paints(texicon.frame_col),
+            frame_size: texicon.frame_size.map(|size| [size.x, size.y]),
+            frame_width: texicon.frame_width,
+            radius: texicon.radius,
+            tooltip_gap: texicon.tooltip_gap,
+            tooltip_position: texicon
+                .tooltip_position
+                .and_then(tooltip_position_name)
+                .map(str::to_owned),
+            enabled: texicon.enabled,
+            selected: texicon.selected,
+        }
+    }
+
+    fn into_texicon(self) -> Result<PlaygroundTexicon, String> {
+        let tooltip_position = match self.tooltip_position {
+            Some(name) => Some(
+                TOOLTIP_POSITIONS
+                    .iter()
+                    .find(|(position, _)| *position == name)
+                    .map(|(_, align)| *align)
+                    .ok_or_else(|| format!("unknown tooltip position {name:?}"))?,
+            ),
+            None => None,
+        };
+        Ok(PlaygroundTexicon {
+            icon: self.icon,
+            text: self.text,
+            show_text: self.show_text,
+            tooltip: self.tooltip,
+            img_size: self.img_size.map(|[x, y]| vec2(x, y)),
+            img_scale_hov: self.img_scale_hov,
+            text_size: self.text_size,
+            img_text_gap: self.img_text_gap,
+            sense: self.sense,
+            bkgnd_col: self.bkgnd_col.to_paints(),
+            img_tint_col: self.img_tint_col.to_paints(),
+            text_col: self.text_col.to_paints(),
+            frame_col: self.frame_col.to_paints(),
+            frame_size: self.frame_size.map(|[x, y]| vec2(x, y)),
+            frame_width: self.frame_width,
+            radius: self.radius,
+            tooltip_gap: self.tooltip_gap,
+            tooltip_position,
+            enabled: self.enabled,
+            selected: self.selected,
+        })
+    }
+}
+
+pub fn to_ron(texicon: &PlaygroundTexicon) -> String {
+    let def = TexiconDef::from_texicon(texicon);
+    ron::ser::to_string_pretty(&def, ron::ser::PrettyConfig::default()).unwrap_or_default()
+}
+
+pub fn to_json(texicon: &PlaygroundTexicon) -> String {
+    serde_json::to_string_pretty(&TexiconDef::from_texicon(texicon)).unwrap_or_default()
+}
+
+// Read a texicon written by `to_ron` or `to_json`. JSON is told apart by its leading `{`.
+pub fn parse(text: &str) -> Result<PlaygroundTexicon, String> {
+    let def: TexiconDef = if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).map_err(|err| format!("Not valid texicon JSON: {err}"))?
+    } else {
+        ron::from_str(text).map_err(|err| format!("Not valid texicon RON: {err}"))?
+    };
+    def.into_texicon()
+}
@@ -0,0 +1,1086 @@
+use egui::{ImageSource, Vec2};
+use egui_widget_texicon::Texicon;
+
+use crate::action::{Action, ContextAction, ContextEntry, TexiEvent, TexiEventKind};
+use crate::animation::AnimationSettings;
+use crate::badge::Badge;
+use crate::bench::{FrameTimes, Stopwatch};
+use crate::menu_layout::ItemRef;
+use crate::palette::{Palette, Role};
+use crate::texi_style::TexiStyle;
+
+// === Constants ===
+#[rustfmt::skip] const FOCUS_RING_ROLE: Role = Role::ACCENT;
+#[rustfmt::skip] const FOCUS_RING_WIDTH: f32 = 2.0;
+#[rustfmt::skip] const FOCUS_RING_GAP: f32   = 4.0;
+#[rustfmt::skip] const FOCUS_RING_RADIUS: u8 = 6;
+// The image and text sizes the texicon picks when a style leaves them to it,
+// for placing badges and growing images on hover
+#[rustfmt::skip] const DEFAULT_IMG_FRACTION: f32 = 0.5;
+#[rustfmt::skip] const DEFAULT_TEXT_SIZE: f32    = 14.0;
+#[rustfmt::skip] const DEFAULT_IMG_TEXT_GAP: f32 = 4.0;
+// Drag and drop
+#[rustfmt::skip] const DROP_LINE_ROLE: Role = Role::ACCENT;
+#[rustfmt::skip] const DROP_LINE_WIDTH: f32 = 3.0;
+#[rustfmt::skip] const GHOST_ALPHA: u8      = 180;
+
+// === Texicon data ===
+#[rustfmt::skip]
+#[derive(Clone)]
+pub struct MyTexicon {
+    pub img:     ImageSource<'static>,
+    pub text:    String,
+    pub tooltip: String,
+    pub enabled: bool,
+    pub action:  Option<Action>,
+    pub style:   ItemStyle,
+    pub badge:   Option<Badge>,
+    // Right-click / long-press menu; none when empty
+    pub context: Vec<ContextEntry>,
+    // Where it was defined in the menu file, for saving the menu layout
+    pub origin:  Option<ItemRef>,
+}
+
+impl MyTexicon {
+    // A copy for another menu: styled by that menu, without the badge the original
+    // keeps updating, and with a context menu to open it or take it off again
+    pub fn pinned(&self) -> Self {
+        Self {
+            style: ItemStyle::default(),
+            badge: None,
+            context: vec![
+                ContextEntry::new("Open", ContextAction::Open),
+                ContextEntry::new("Unpin", ContextAction::Unpin),
+            ],
+            ..self.clone()
+        }
+    }
+
+    // Stays the same while the texicon moves along its menu: where it was defined,
+    // else its action, else its text
+    pub fn stable_id(&self) -> egui::Id {
+        match (&self.origin, self.action) {
+            (Some(origin), _) => egui::Id::new(origin),
+            (None, Some(action)) => egui::Id::new(action),
+            (None, None) => egui::Id::new(&self.text),
+        }
+    }
+}
+
+// Per-item overrides, applied on top of the menu style
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ItemStyle {
+    pub img_size:      Option<[f32; 2]>,
+    pub img_scale_hov: Option<f32>,
+    pub text_size:     Option<f32>,
+    pub img_text_gap:  Option<f32>,
+    pub frame_size:    Option<[f32; 2]>,
+    pub frame_width:   Option<f32>,
+    pub radius:        Option<u8>,
+    pub tooltip_gap:   Option<f32>,
+}
+
+impl ItemStyle {
+    // Sizes are multiplied by `scale`, for menus that shrink to fit
+    pub fn apply(&self, mut texi: Texicon<'static>, scale: f32) -> Texicon<'static> {
+        if let Some([x, y]) = self.img_size {
+            texi = texi.img_size(egui::vec2(x, y) * scale);
+        }
+        if let Some(scale_hov) = self.img_scale_hov {
+            texi = texi.img_scale_hov(scale_hov);
+        }
+        if let Some(size) = self.text_size {
+            texi = texi.text_size(size * scale);
+        }
+        if let Some(gap) = self.img_text_gap {
+            texi = texi.img_text_gap(gap * scale);
+        }
+        if let Some([x, y]) = self.frame_size {
+            texi = texi.frame_size(egui::vec2(x, y) * scale);
+        }
+        if let Some(width) = self.frame_width {
+            texi = texi.frame_width(width);
+        }
+        if let Some(radius) = self.radius {
+            texi = texi.radius(radius);
+        }
+        if let Some(gap) = self.tooltip_gap {
+            texi = texi.tooltip_gap(gap);
+        }
+        texi
+    }
+}
+
+// How the texicons of a menu are laid out in the available space
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TexiLayout {
+    // A single row, centered horizontally and vertically
+    Horizontal,
+    // A single column, centered horizontally and starting at the top
+    Vertical,
+}
+
+// What a menu does when its texicons don't fit the available space
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Overflow {
+    // Draw them anyway, past the edges
+    #[default]
+    Visible,
+    // Start a new row (or column)
+    Wrap,
+    // Scroll along the menu
+    Scroll,
+    // Scale the texicons down, but not below `min_scale`
+    Shrink {
+        min_scale: f32,
+    },
+}
+
+// What clicking a texicon (or Enter/Space on it) does to the selection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+    // Exactly one texicon is selected, like tabs
+    #[default]
+    Single,
+    // At most one; clicking the selected texicon deselects it
+    SingleWithDeselect,
+    // Any number, each click toggles one. Shift-click selects the range from
+    // the last clicked texicon, Ctrl+Shift-click adds that range to the selection.
+    MultipleToggle,
+    // Action buttons: nothing is ever selected
+    None,
+}
+
+// How the menu is presented to screen readers (AccessKit)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuRole {
+    // Navigation: a tab list of tabs
+    TabList,
+    // A choice between options: a radio group of radio buttons
+    RadioGroup,
+}
+
+// What dragging texicons does in a menu
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dragging {
+    // The texicons stay where they are
+    #[default]
+    Off,
+    // Texicons can be dragged onto other menus, which get a pinned copy
+    CopyOut,
+    // Texicons can be dragged along the menu to reorder it, or onto other menus.
+    // Texicons dragged from other menus can be dropped in.
+    Reorder,
+}
+
+// The drag and drop payload: a texicon on its way to another place, in its menu or another
+#[derive(Clone)]
+struct TexiDrag {
+    // The label of the menu it comes from
+    from: String,
+    item: usize,
+    texicon: MyTexicon,
+    // For the ghost under the pointer
+    img_size: Vec2,
+}
+
+// What the pointer did to the texicons during one draw
+#[derive(Default)]
+struct PointerEvents {
+    clicked: Option<usize>,
+    double_clicked: Option<usize>,
+    middle_clicked: Option<usize>,
+    hovered: Option<usize>,
+    context_requested: Option<usize>,
+    context_chosen: Option<(usize, ContextAction)>,
+}
+
+// === Texicon menu ===
+#[rustfmt::skip]
+pub struct TexiMenu {
+    label:       String,
+    role:        MenuRole,
+    items:       Vec<MyTexicon>,
+    layout:      TexiLayout,
+    overflow:    Overflow,
+    texi_size:   Vec2,
+    gap:         f32,
+    presets:     Vec<TexiStyle>,
+    selection:   SelectionMode,
+    selected:    Vec<bool>,
+    dragging:    Dragging,
+    // Where a Shift-click range starts
+    anchor:      Option<usize>,
+    // Keyboard focus: whether this menu has it and which texicon has the focus ring
+    has_focus:   bool,
+    focused:     usize,
+    took_focus:  bool,
+    // Each texicon's egui id in the last frame, and in which pass the menu was drawn
+    item_ids:    Vec<egui::Id>,
+    drawn_pass:  Option<u64>,
+    // The texicon under the pointer, and what happened during this draw
+    hovered:     Option<usize>,
+    events:      Vec<TexiEvent>,
+    // Where each texicon was drawn in the last frame
+    item_rects:  Vec<egui::Rect>,
+    frame_times: FrameTimes,
+}
+
+impl TexiMenu {
+    pub fn new(
+        items: Vec<MyTexicon>,
+        layout: TexiLayout,
+        texi_size: Vec2,
+        gap: f32,
+        presets: Vec<TexiStyle>,
+    ) -> Self {
+        Self {
+            label: String::new(),
+            role: MenuRole::TabList,
+            selection: SelectionMode::default(),
+            selected: vec![false; items.len()],
+            dragging: Dragging::default(),
+            anchor: None,
+            items,
+            layout,
+            overflow: Overflow::default(),
+            texi_size,
+            gap,
+            presets,
+            has_focus: false,
+            focused: 0,
+            took_focus: false,
+            item_ids: Vec::new(),
+            drawn_pass: None,
+            hovered: None,
+            events: Vec::new(),
+            item_rects: Vec::new(),
+            frame_times: FrameTimes::default(),
+        }
+    }
+    // The name screen readers announce for the menu
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+    pub fn role(mut self, role: MenuRole) -> Self {
+        self.role = role;
+        self
+    }
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+    pub fn selection(mut self, mode: SelectionMode) -> Self {
+        self.set_selection_mode(mode);
+        self
+    }
+    pub fn dragging(mut self, dragging: Dragging) -> Self {
+        self.dragging = dragging;
+        self
+    }
+    pub fn selection_mode(&self) -> SelectionMode {
+        self.selection
+    }
+    // Change the mode, trimming the selection to what the new mode allows
+    pub fn set_selection_mode(&mut self, mode: SelectionMode) {
+        self.selection = mode;
+        self.anchor = None;
+        match mode {
+            SelectionMode::Single | SelectionMode::SingleWithDeselect => {
+                if let Some(first) = self.selected_texicon() {
+                    self.set_selected_texicon(first);
+                }
+            }
+            SelectionMode::MultipleToggle => {}
+            SelectionMode::None => self.clear_selection(),
+        }
+    }
+    pub fn clear_selection(&mut self) {
+        self.selected.fill(false);
+    }
+    // Set a texicon to selected state (at startup)
+    pub fn set_selected_texicon(&mut self, index: usize) {
+        if index < self.selected.len() {
+            self.selected.fill(false);
+            self.selected[index] = true;
+        }
+    }
+    // The style preset of item `idx`. The last preset is used for any remaining items.
+    fn preset(&self, idx: usize) -> Option<&TexiStyle> {
+        self.presets.get(idx).or_else(|| self.presets.last())
+    }
+    pub fn is_selected(&self, idx: usize) -> bool {
+        self.selected.get(idx).copied().unwrap_or_default()
+    }
+    pub fn item_count(&self) -> usize {
+        self.items.len()
+    }
+    pub fn item_text(&self, idx: usize) -> Option<&str> {
+        self.items.get(idx).map(|texicon| texicon.text.as_str())
+    }
+    pub fn item(&self, idx: usize) -> Option<&MyTexicon> {
+        self.items.get(idx)
+    }
+    pub fn items(&self) -> &[MyTexicon] {
+        &self.items
+    }
+    // Where the texicon with `MyTexicon::stable_id` `id` is now
+    pub fn item_with_stable_id(&self, id: egui::Id) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|texicon| texicon.stable_id() == id)
+    }
+    // Replace every texicon, e.g. with a saved layout. Nothing is selected afterwards.
+    pub fn set_items(&mut self, items: Vec<MyTexicon>) {
+        self.selected = vec![false; items.len()];
+        self.items = items;
+        self.anchor = None;
+        self.hovered = None;
+        self.focused = 0;
+    }
+    // Whether the menu shows the texicon defined at `origin`, or a copy of it
+    pub fn has_origin(&self, origin: &ItemRef) -> bool {
+        self.items
+            .iter()
+            .any(|texicon| texicon.origin.as_ref() == Some(origin))
+    }
+    // Add a texicon at the end of the menu, unselected
+    pub fn push_item(&mut self, texicon: MyTexicon) {
+        self.insert_item(self.items.len(), texicon);
+    }
+    // Add a texicon before item `idx` (or at the end), unselected
+    pub fn insert_item(&mut self, idx: usize, texicon: MyTexicon) {
+        let idx = idx.min(self.items.len());
+        self.items.insert(idx, texicon);
+        self.selected.insert(idx, false);
+        self.anchor = None;
+        self.hovered = None;
+    }
+    // Move texicon `from` to index `to`, keeping its selection. The focus ring goes with it.
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        if from >= self.items.len() || to >= self.items.len() {
+            return;
+        }
+        let texicon = self.items.remove(from);
+        let selected = self.selected.remove(from);
+        self.items.insert(to, texicon);
+        self.selected.insert(to, selected);
+        self.anchor = None;
+        self.hovered = None;
+        self.focused = to;
+    }
+    // Take texicon `idx` off the menu. The focus ring stays in range.
+    pub fn remove_item(&mut self, idx: usize) -> Option<MyTexicon> {
+        if idx >= self.items.len() {
+            return None;
+        }
+        self.selected.remove(idx);
+        self.anchor = None;
+        self.hovered = None;
+        self.focused = self.focused.min(self.items.len().saturating_sub(2));
+        Some(self.items.remove(idx))
+    }
+    // Swap the image of texicon `idx`, e.g. for an imported icon
+    pub fn set_image(&mut self, idx: usize, img: ImageSource<'static>) {
+        if let Some(texicon) = self.items.get_mut(idx) {
+            texicon.img = img;
+        }
+    }
+    pub fn badge(&self, idx: usize) -> Option<&Badge> {
+        self.items
+            .get(idx)
+            .and_then(|texicon| texicon.badge.as_ref())
+    }
+    // Show, change or remove the badge of texicon `idx`
+    pub fn set_badge(&mut self, idx: usize, badge: Option<Badge>) {
+        if let Some(texicon) = self.items.get_mut(idx) {
+            texicon.badge = badge;
+        }
+    }
+    pub fn is_enabled(&self, idx: usize) -> bool {
+        self.items.get(idx).is_some_and(|texicon| texicon.enabled)
+    }
+    // The first texicon with this action
+    pub fn position_of(&self, action: Action) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|texicon| texicon.action == Some(action))
+    }
+    // The selected texicons, in menu order
+    pub fn selected_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selected
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &selected)| selected.then_some(idx))
+    }
+    // Get the index of the selected texicon (for saving)
+    pub fn selected_texicon(&self) -> Option<usize> {
+        self.selected.iter().position(|&selected| selected)
+    }
+    // Give or take the keyboard focus. The focus ring starts on the selected texicon.
+    pub fn set_keyboard_focus(&mut self, has_focus: bool) {
+        if has_focus && !self.has_focus {
+            self.focused = self.selected_texicon().unwrap_or_default();
+        }
+        self.has_focus = has_focus;
+    }
+    pub fn has_keyboard_focus(&self) -> bool {
+        self.has_focus
+    }
+    // Whether a click moved the keyboard focus to this menu during the last draw
+    pub fn took_focus(&self) -> bool {
+        self.took_focus
+    }
+    // The texicon with the focus ring, while the menu has the keyboard focus
+    pub fn focused_item(&self) -> Option<usize> {
+        self.has_focus.then_some(self.focused)
+    }
+    // The egui id texicon `idx` was drawn with in the last frame
+    pub fn item_id(&self, idx: usize) -> Option<egui::Id> {
+        self.item_ids
+            .get(idx)
+            .copied()
+            .filter(|&id| id != egui::Id::NULL)
+    }
+    // Which texicon was drawn with egui id `id`, if one of this menu's was
+    pub fn item_with_id(&self, id: egui::Id) -> Option<usize> {
+        self.item_ids.iter().position(|&item_id| item_id == id)
+    }
+    // Whether the menu was drawn in the last frame (or already in this one)
+    pub fn is_shown(&self, ctx: &egui::Context) -> bool {
+        self.drawn_pass
+            .is_some_and(|pass| pass + 1 >= ctx.cumulative_pass_nr())
+    }
+    // Arrow keys move the focus ring along the menu, Home/End jump to the ends,
+    // Enter/Space select the focused texicon and Escape gives up the focus.
+    fn handle_keys(&mut self, ui: &egui::Ui) {
+        use egui::{Key, Modifiers};
+
+        let (prev_key, next_key) = match self.layout {
+            TexiLayout::Horizontal => (Key::ArrowLeft, Key::ArrowRight),
+            TexiLayout::Vertical => (Key::ArrowUp, Key::ArrowDown),
+        };
+        let last = self.items.len().saturating_sub(1);
+
+        let mut activate = false;
+        ui.input_mut(|i| {
+            if i.consume_key(Modifiers::NONE, prev_key) {
+                self.focused = self.focused.saturating_sub(1);
+            }
+            if i.consume_key(Modifiers::NONE, next_key) {
+                self.focused = (self.focused + 1).min(last);
+            }
+            if i.consume_key(Modifiers::NONE, Key::Home) {
+                self.focused = 0;
+            }
+            if i.consume_key(Modifiers::NONE, Key::End) {
+                self.focused = last;
+            }
+            if i.consume_key(Modifiers::NONE, Key::Escape) {
+                self.has_focus = false;
+            }
+            activate = i.consume_key(Modifiers::NONE, Key::Enter)
+                || i.consume_key(Modifiers::NONE, Key::Space);
+        });
+
+        let enabled = self
+            .items
+            .get(self.focused)
+            .is_some_and(|item| item.enabled);
+        if activate && enabled {
+            self.activate(self.focused, Modifiers::NONE);
+            self.emit(TexiEventKind::Activated, self.focused);
+        }
+    }
+    // Update the selection for a click on (or Enter/Space at) texicon `idx`
+    fn activate(&mut self, idx: usize, modifiers: egui::Modifiers) {
+        match self.selection {
+            SelectionMode::Single => self.set_selected_texicon(idx),
+            SelectionMode::SingleWithDeselect => {
+                if self.is_selected(idx) {
+                    self.clear_selection();
+                } else {
+                    self.set_selected_texicon(idx);
+                }
+            }
+            SelectionMode::MultipleToggle => match self.anchor {
+                Some(anchor) if modifiers.shift => {
+                    if !modifiers.command {
+                        self.clear_selection();
+                    }
+                    // Disabled texicons inside the range stay as they are
+                    let (first, last) = (anchor.min(idx), anchor.max(idx));
+                    let range = self.items.iter().zip(&mut self.selected);
+                    for (item, selected) in range.skip(first).take(last - first + 1) {
+                        *selected |= item.enabled;
+                    }
+                }
+                _ => {
+                    if let Some(selected) = self.selected.get_mut(idx) {
+                        *selected = !*selected;
+                    }
+                    self.anchor = Some(idx);
+                }
+            },
+            SelectionMode::None => {}
+        }
+    }
+    // -------------------------
+    // AccessKit (screen readers)
+    // -------------------------
+    fn describe_group(&self, ui: &egui::Ui) {
+        use egui::accesskit::{Orientation, Role};
+
+        ui.ctx().accesskit_node_builder(ui.unique_id(), |node| {
+            node.set_role(match self.role {
+                MenuRole::TabList => Role::TabList,
+                MenuRole::RadioGroup => Role::RadioGroup,
+            });
+            node.set_label(self.label.clone());
+            node.set_orientation(match self.layout {
+                TexiLayout::Horizontal => Orientation::Horizontal,
+                TexiLayout::Vertical => Orientation::Vertical,
+            });
+            if self.selection == SelectionMode::MultipleToggle {
+                node.set_multiselectable();
+            }
+        });
+    }
+    // Overrides whatever the texicon widget reported about itself
+    fn describe_item(&self, ui: &egui::Ui, resp: &egui::Response, idx: usize) {
+        use egui::accesskit::{Role, Toggled};
+
+        let Some(texicon) = self.items.get(idx) else {
+            return;
+        };
+        let selected = self.selected.get(idx).copied().unwrap_or_default();
+        // Badges are read after the text, e.g. "Inbox, 3"
+        let label = match texicon.badge.as_ref().filter(|badge| badge.is_visible()) {
+            Some(badge) => format!(
+                "{}, {}",
+                texicon.text,
+                badge.label().unwrap_or_else(|| "badge".to_owned())
+            ),
+            None => texicon.text.clone(),
+        };
+        ui.ctx().accesskit_node_builder(resp.id, |node| {
+            node.set_label(label);
+            node.set_description(texicon.tooltip.clone());
+            match self.role {
+                MenuRole::TabList => {
+                    node.set_role(Role::Tab);
+                    node.set_selected(selected);
+                }
+                MenuRole::RadioGroup => {
+                    node.set_role(Role::RadioButton);
+                    node.set_toggled(if selected {
+                        Toggled::True
+                    } else {
+                        Toggled::False
+                    });
+                }
+            }
+            if texicon.enabled {
+                node.clear_disabled();
+            } else {
+                node.set_disabled();
+            }
+        });
+    }
+    // ------
+    // Layout
+    // ------
+    // The height of the rows a horizontal menu wraps into when it's `width` wide
+    pub fn wrapped_height(&self, width: f32) -> f32 {
+        let per_line = ((width + self.gap) / (self.texi_size.x + self.gap))
+            .floor()
+            .max(1.0);
+        let lines = (self.items.len() as f32 / per_line).ceil();
+        lines * self.texi_size.y + (lines - 1.0).max(0.0) * self.gap
+    }
+    // The size needed to show every texicon at full size, without wrapping
+    fn content_size(&self) -> Vec2 {
+        let count = self.items.len() as f32;
+        let along = |size: f32| size * count + self.gap * (count - 1.0).max(0.0);
+        match self.layout {
+            TexiLayout::Horizontal => egui::vec2(along(self.texi_size.x), self.texi_size.y),
+            TexiLayout::Vertical => egui::vec2(self.texi_size.x, along(self.texi_size.y)),
+        }
+    }
+    // The rect of every texicon inside `rect`, and the scale they are drawn at
+    fn texi_rects(&self, rect: egui::Rect) -> (Vec<egui::Rect>, f32) {
+        let count = self.items.len();
+        let horizontal = self.layout == TexiLayout::Horizontal;
+
+        // "Main" runs along the menu, "cross" across it
+        let (avail_main, size_main, size_cross) = if horizontal {
+            (rect.width(), self.texi_size.x, self.texi_size.y)
+        } else {
+            (rect.height(), self.texi_size.y, self.texi_size.x)
+        };
+        let full_main = size_main * count as f32 + self.gap * (count as f32 - 1.0).max(0.0);
+
+        let scale = match self.overflow {
+            Overflow::Shrink { min_scale } if full_main > 0.0 => {
+                (avail_main / full_main).clamp(min_scale.min(1.0), 1.0)
+            }
+            _ => 1.0,
+        };
+        let (size_main, size_cross, gap) =
+            (size_main * scale, size_cross * scale, self.gap * scale);
+
+        let per_line = match self.overflow {
+            Overflow::Wrap => ((avail_main + gap) / (size_main + gap)).floor() as usize,
+            _ => count,
+        }
+        .clamp(1, count.max(1));
+        let lines = count.div_ceil(per_line);
+        let block_cross = size_cross * lines as f32 + gap * (lines as f32 - 1.0).max(0.0);
+
+        // Rows are centered both ways, columns are centered across and start at the top
+        let cross_start = if horizontal {
+            rect.center().y - block_cross / 2.0
+        } else {
+            rect.center().x - block_cross / 2.0
+        };
+        let main_start = |in_line: usize| {
+            if horizontal {
+                let line_main = size_main * in_line as f32 + gap * (in_line as f32 - 1.0);
+                rect.center().x - line_main / 2.0
+            } else {
+                rect.min.y
+            }
+        };
+
+        let rects = (0..count)
+            .map(|idx| {
+                let line = idx / per_line;
+                let in_line = per_line.min(count - line * per_line);
+                let main = main_start(in_line) + (idx % per_line) as f32 * (size_main + gap);
+                let cross = cross_start + line as f32 * (size_cross + gap);
+                let (pos, size) = if horizontal {
+                    (egui::pos2(main, cross), egui::vec2(size_main, size_cross))
+                } else {
+                    (egui::pos2(cross, main), egui::vec2(size_cross, size_main))
+                };
+                egui::Rect::from_min_size(pos, size)
+            })
+            .collect();
+        (rects, scale)
+    }
+
+    fn emit(&mut self, kind: TexiEventKind, item: usize) {
+        let action = self.items.get(item).and_then(|texicon| texicon.action);
+        self.events.push(TexiEvent { kind, item, action });
+    }
+
+    // Draws the menu and returns what happened to its texicons, in order
+    pub fn draw_texicons(&mut self, ui: &mut egui::Ui) -> Vec<TexiEvent> {
+        self.took_focus = false;
+        // Another widget (e.g. a text field) has egui's focus, so the keys are its
+        let focus = ui.memory(|mem| mem.focused());
+        if focus.is_some_and(|id| self.item_with_id(id).is_none()) {
+            self.has_focus = false;
+        }
+        if self.has_focus {
+            self.handle_keys(ui);
+        }
+
+        if self.overflow == Overflow::Scroll {
+            let scroll_area = match self.layout {
+                TexiLayout::Horizontal => egui::ScrollArea::horizontal(),
+                TexiLayout::Vertical => egui::ScrollArea::vertical(),
+            };
+            scroll_area
+                .id_salt(&self.label)
+                .auto_shrink(false)
+                .show(ui, |ui| self.draw_in(ui));
+        } else {
+            self.draw_in(ui);
+        }
+        std::mem::take(&mut self.events)
+    }
+
+    // Where the texicon puts the image of item `idx`: centred, above the text if any
+    fn image_rect(&self, idx: usize, texi_rect: egui::Rect, scale: f32) -> egui::Rect {
+        let preset = self.preset(idx).map(|preset| preset.scaled(scale));
+        let style = self.items.get(idx).map(|texicon| &texicon.style);
+        let img_size = style
+            .and_then(|style| style.img_size)
+            .map(|[x, y]| egui::vec2(x, y) * scale)
+            .or(preset.and_then(|preset| preset.img_size))
+            .unwrap_or(texi_rect.size() * DEFAULT_IMG_FRACTION);
+        let below = if preset.is_some_and(|preset| preset.show_text) {
+            let text_size = style
+                .and_then(|style| style.text_size)
+                .map(|size| size * scale)
+                .or(preset.and_then(|preset| preset.text_size))
+                .unwrap_or(DEFAULT_TEXT_SIZE * scale);
+            let gap = style
+                .and_then(|style| style.img_text_gap)
+                .map(|gap| gap * scale)
+                .or(preset.and_then(|preset| preset.img_text_gap))
+                .unwrap_or(DEFAULT_IMG_TEXT_GAP * scale);
+            text_size + gap
+        } else {
+            0.0
+        };
+        let top = texi_rect.center().y - (img_size.y + below) / 2.0;
+        egui::Rect::from_min_size(
+            egui::pos2(texi_rect.center().x - img_size.x / 2.0, top),
+            img_size,
+        )
+    }
+
+    // The texicon for item `idx`, styled for its place and state.
+    // With animations on, its colours and hover scale are part way to the new state.
+    fn texicon(
+        &self,
+        ctx: &egui::Context,
+        idx: usize,
+        texi_rect: egui::Rect,
+        scale: f32,
+        palette: &Palette,
+        animation: AnimationSettings,
+    ) -> Option<Texicon<'static>> {
+        let texicon = self.items.get(idx)?;
+        let selected = self.selected.get(idx).copied().unwrap_or_default();
+        let preset = self.preset(idx).map(|preset| preset.scaled(scale));
+
+        let mut texi = Texicon::new(texicon.img.clone())
+            .enabled(texicon.enabled)
+            .selected(selected)
+            .frame_size(texi_rect.size())
+            .tooltip_text(texicon.tooltip.clone());
+        if let Some(preset) = &preset {
+            texi = preset.apply(texi, &texicon.text, palette);
+        }
+        texi = texicon.style.apply(texi, scale);
+        if !animation.animates() {
+            return Some(texi);
+        }
+
+        // Keyed by the texicon rather than its index, so a reorder doesn't swap animations
+        let id = egui::Id::new(&self.label).with(texicon.stable_id());
+        let sel = animation.blend(ctx, id.with("sel"), selected);
+        let hov = animation.blend(
+            ctx,
+            id.with("hov"),
+            texicon.enabled && self.hovered == Some(idx),
+        );
+        if let Some(preset) = &preset {
+            texi = preset.apply_blended(texi, palette, sel, hov);
+        }
+        // Grow the image ourselves, from its styled size or the one the texicon picks
+        let img_size = texicon
+            .style
+            .img_size
+            .map(|[x, y]| egui::vec2(x, y) * scale)
+            .or(preset.and_then(|preset| preset.img_size))
+            .unwrap_or(texi_rect.size() * DEFAULT_IMG_FRACTION);
+        let scale_hov = texicon
+            .style
+            .img_scale_hov
+            .or(preset.and_then(|preset| preset.img_scale_hov));
+        if let Some(scale_hov) = scale_hov {
+            texi = texi
+                .img_size(img_size * egui::lerp(1.0..=scale_hov, hov))
+                .img_scale_hov(1.0);
+        }
+        Some(texi)
+    }
+
+    fn draw_in(&mut self, ui: &mut egui::Ui) {
+        // Get the palette directly (cheap), fading to it after a theme switch
+        let animation = AnimationSettings::get(ui.ctx());
+        let palette = animation.palette(ui.ctx(), Palette::current(ui.ctx()));
+
+        // Allocate space for the texicons.
+        // When scrolling, that is at least the space needed for all of them.
+        let mut size = ui.available_size();
+        if self.overflow == Overflow::Scroll {
+            size = size.max(self.content_size());
+        }
+        let (_id, rect) = ui.allocate_space(size);
+        let (texi_rects, scale) = self.texi_rects(rect);
+        self.item_rects.clone_from(&texi_rects);
+        self.item_ids = vec![egui::Id::NULL; self.items.len()];
+        self.drawn_pass = Some(ui.ctx().cumulative_pass_nr());
+
+        // ------------------------
+        // Timing the Texicons loop
+        // ------------------------
+        let stopwatch = Stopwatch::start();
+
+        // -----------------
+        // Draw the Texicons
+        // -----------------
+        // In a child ui, so screen readers see the texicons as children of one group
+        let mut pointer = PointerEvents::default();
+        let group = egui::UiBuilder::new().id_salt(&self.label).max_rect(rect);
+        ui.scope_builder(group, |ui| {
+            self.describe_group(ui);
+
+            for (idx, texi_rect) in texi_rects.into_iter().enumerate() {
+                let texicon = self.texicon(ui.ctx(), idx, texi_rect, scale, &palette, animation);
+                let Some(texi) = texicon else {
+                    continue;
+                };
+                if self.dragging != Dragging::Off && self.is_enabled(idx) {
+                    self.drag_source(ui, idx, texi_rect, scale);
+                }
+                let resp = ui.put(texi_rect, texi);
+                if let Some(id) = self.item_ids.get_mut(idx) {
+                    *id = resp.id;
+                }
+                if let Some(badge) = self.badge(idx) {
+                    let img_rect = self.image_rect(idx, texi_rect, scale);
+                    badge.paint(ui.painter(), img_rect, &palette);
+                }
+                self.describe_item(ui, &resp, idx);
+                // Click response
+                if resp.clicked() {
+                    pointer.clicked = Some(idx);
+                }
+                if resp.double_clicked() {
+                    pointer.double_clicked = Some(idx);
+                }
+                if resp.middle_clicked() {
+                    pointer.middle_clicked = Some(idx);
+                }
+                if resp.hovered() {
+                    pointer.hovered = Some(idx);
+                }
+                if resp.secondary_clicked() || resp.long_touched() {
+                    pointer.context_requested = Some(idx);
+                }
+                if let Some(action) = self.context_menu(&resp, idx) {
+                    pointer.context_chosen = Some((idx, action));
+                }
+                // Focus ring
+                if self.has_focus && self.focused == idx {
+                    ui.painter().rect_stroke(
+                        resp.rect.expand(FOCUS_RING_GAP),
+                        FOCUS_RING_RADIUS,
+                        egui::Stroke::new(FOCUS_RING_WIDTH, palette.get(FOCUS_RING_ROLE)),
+                        egui::StrokeKind::Outside,
+                    );
+                }
+            }
+        });
+        self.handle_pointer(ui, &pointer);
+        self.drag_and_drop(ui, rect, &palette);
+
+        // ------------------------
+        // Timing the Texicons loop
+        // ------------------------
+        self.frame_times.push(stopwatch.elapsed_us());
+    }
+
+    // The context menu of texicon `idx`, if it has one. Returns the entry picked this frame.
+    fn context_menu(&self, resp: &egui::Response, idx: usize) -> Option<ContextAction> {
+        let entries = &self.items.get(idx)?.context;
+        if entries.is_empty() {
+            return None;
+        }
+        let mut chosen = None;
+        resp.context_menu(|ui| {
+            for entry in entries {
+                if ui.button(&entry.text).clicked() {
+                    chosen = Some(entry.action);
+                    ui.close();
+                }
+            }
+        });
+        chosen
+    }
+
+    // -------------
+    // Drag and drop
+    // -------------
+    // Let texicon `idx` be dragged. Sensing drags underneath the texicon leaves its clicks alone.
+    fn drag_source(&self, ui: &egui::Ui, idx: usize, texi_rect: egui::Rect, scale: f32) {
+        let id = ui.id().with(("drag", idx));
+        let resp = ui.interact(texi_rect, id, egui::Sense::DRAG);
+        if !resp.drag_started() {
+            return;
+        }
+        if let Some(texicon) = self.items.get(idx) {
+            let drag = TexiDrag {
+                from: self.label.clone(),
+                item: idx,
+                texicon: texicon.clone(),
+                img_size: self.image_rect(idx, texi_rect, scale).size(),
+            };
+            egui::DragAndDrop::set_payload(ui.ctx(), drag);
+        }
+    }
+
+    // While a texicon is dragged: the ghost under the pointer if it comes from this menu,
+    // and the drop line if it's over this menu (`rect`) and may be dropped here
+    fn drag_and_drop(&mut self, ui: &egui::Ui, rect: egui::Rect, palette: &Palette) {
+        let Some(drag) = egui::DragAndDrop::payload::<TexiDrag>(ui.ctx()) else {
+            return;
+        };
+        let Some(pointer) = ui.ctx().pointer_latest_pos() else {
+            return;
+        };
+        if drag.from == self.label {
+            paint_ghost(ui.ctx(), &drag, pointer);
+        }
+        if !rect.intersect(ui.clip_rect()).contains(pointer) {
+            return;
+        }
+        let Some(target) = self.drop_target(&drag, pointer) else {
+            return;
+        };
+        self.paint_drop_line(ui.painter(), target, palette);
+        if ui.input(|i| i.pointer.any_released()) {
+            egui::DragAndDrop::clear_payload(ui.ctx());
+            self.drop_here(&drag, target);
+        }
+    }
+
+    // Where a texicon dragged to `pointer` would go: before the texicon at that index,
+    // or at the end. `None` if it can't be dropped here.
+    fn drop_target(&self, drag: &TexiDrag, pointer: egui::Pos2) -> Option<usize> {
+        if self.dragging != Dragging::Reorder {
+            return None;
+        }
+        let from_here = drag.from == self.label;
+        let origin = drag.texicon.origin.as_ref();
+        // Each texicon of the menu file once per menu
+        if !from_here && origin.is_some_and(|origin| self.has_origin(origin)) {
+            return None;
+        }
+        // Next to the nearest texicon, on the side the pointer is
+        let nearest = self.item_rects.iter().enumerate().min_by(|(_, a), (_, b)| {
+            a.distance_sq_to_pos(pointer)
+                .total_cmp(&b.distance_sq_to_pos(pointer))
+        });
+        let Some((idx, rect)) = nearest else {
+            return Some(0);
+        };
+        let after = match self.layout {
+            TexiLayout::Horizontal => pointer.x > rect.center().x,
+            TexiLayout::Vertical => pointer.y > rect.center().y,
+        };
+        let target = idx + usize::from(after);
+        // Right before or after itself, a texicon stays where it is
+        if from_here && (target == drag.item || target == drag.item + 1) {
+            return None;
+        }
+        Some(target)
+    }
+
+    // A line across the gap before texicon `target`, or after the last texicon
+    fn paint_drop_line(&self, painter: &egui::Painter, target: usize, palette: &Palette) {
+        let stroke = egui::Stroke::new(DROP_LINE_WIDTH, palette.get(DROP_LINE_ROLE));
+        let (rect, before) = match self.item_rects.get(target) {
+            Some(rect) => (*rect, true),
+            None => match self.item_rects.last() {
+                Some(rect) => (*rect, false),
+                None => return,
+            },
+        };
+        let half_gap = self.gap / 2.0;
+        match self.layout {
+            TexiLayout::Horizontal => {
+                let x = if before {
+                    rect.left() - half_gap
+                } else {
+                    rect.right() + half_gap
+                };
+                painter.vline(x, rect.y_range(), stroke);
+            }
+            TexiLayout::Vertical => {
+                let y = if before {
+                    rect.top() - half_gap
+                } else {
+                    rect.bottom() + half_gap
+                };
+                painter.hline(rect.x_range(), y, stroke);
+            }
+        }
+    }
+
+    // Move a texicon of this menu, or add a pinned copy of one from another menu
+    fn drop_here(&mut self, drag: &TexiDrag, target: usize) {
+        if drag.from == self.label {
+            let from = drag.item;
+            let to = if target > from { target - 1 } else { target };
+            self.move_item(from, to);
+            self.emit(TexiEventKind::Moved { from }, to);
+        } else {
+            let at = target.min(self.items.len());
+            self.insert_item(at, drag.texicon.pinned());
+            self.emit(TexiEventKind::DroppedIn, at);
+        }
+    }
+
+    // Update the selection, focus and hover state, and emit the events, in a fixed order
+    fn handle_pointer(&mut self, ui: &egui::Ui, pointer: &PointerEvents) {
+        if let Some(idx) = pointer.clicked {
+            let modifiers = ui.input(|i| i.modifiers);
+            self.activate(idx, modifiers);
+            self.focused = idx;
+            self.took_focus = !self.has_focus;
+            self.has_focus = true;
+            self.emit(TexiEventKind::Activated, idx);
+        }
+        if let Some(idx) = pointer.double_clicked {
+            self.emit(TexiEventKind::DoubleClicked, idx);
+        }
+        if let Some(idx) = pointer.middle_clicked {
+            self.emit(TexiEventKind::MiddleClicked, idx);
+        }
+        if pointer.hovered != self.hovered {
+            if let Some(idx) = self.hovered {
+                self.emit(TexiEventKind::HoverLeft, idx);
+            }
+            if let Some(idx) = pointer.hovered {
+                self.emit(TexiEventKind::HoverEntered, idx);
+            }
+            self.hovered = pointer.hovered;
+        }
+        if let Some(idx) = pointer.context_requested {
+            self.emit(TexiEventKind::ContextRequested, idx);
+        }
+        if let Some((idx, action)) = pointer.context_chosen {
+            self.emit(TexiEventKind::ContextChosen(action), idx);
+        }
+    }
+
+    // Where texicon `idx` was drawn in the last frame, e.g. to aim the pointer at it
+    pub fn item_rect(&self, idx: usize) -> Option<egui::Rect> {
+        self.item_rects.get(idx).copied()
+    }
+    pub fn frame_times(&self) -> &FrameTimes {
+        &self.frame_times
+    }
+    pub fn frame_times_mut(&mut self) -> &mut FrameTimes {
+        &mut self.frame_times
+    }
+    pub fn name(&self) -> &str {
+        &self.label
+    }
+}
+
+// The dragged texicon's image, see-through, centred under the pointer
+fn paint_ghost(ctx: &egui::Context, drag: &TexiDrag, pointer: egui::Pos2) {
+    ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+    egui::Area::new(egui::Id::new("texicon_drag_ghost"))
+        .order(egui::Order::Tooltip)
+        .fixed_pos(pointer - drag.img_size / 2.0)
+        .interactable(false)
+        .show(ctx, |ui| {
+            ui.add(
+                egui::Image::new(drag.texicon.img.clone())
+                    .fit_to_exact_size(drag.img_size)
+                    .tint(egui::Color32::from_white_alpha(GHOST_ALPHA)),
+            );
+        });
+}
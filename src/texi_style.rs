@@ -0,0 +1,187 @@
+use egui::{RectAlign, Vec2};
+use egui_widget_texicon::{TexiSense, Texicon};
+
+use crate::palette::{Palette, Shade};
+
+// Colours for the normal, selected and hovered states
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StateShades {
+    pub normal: Shade,
+    pub sel: Shade,
+    pub hov: Shade,
+}
+
+impl StateShades {
+    pub const fn new(normal: Shade, sel: Shade, hov: Shade) -> Self {
+        Self { normal, sel, hov }
+    }
+
+    // The same colour in every state
+    pub const fn all(shade: Shade) -> Self {
+        Self::new(shade, shade, shade)
+    }
+}
+
+// Which part of the texicon responds to the mouse
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum SenseArea {
+    Frame,
+    ImageAndText,
+}
+
+impl SenseArea {
+    pub fn texi_sense(self) -> TexiSense {
+        match self {
+            Self::Frame => TexiSense::Frame,
+            Self::ImageAndText => TexiSense::ImageAndText,
+        }
+    }
+}
+
+// Where a tooltip may go, named like the `RectAlign` constants
+#[rustfmt::skip]
+pub const TOOLTIP_POSITIONS: [(&str, RectAlign); 12] = [
+    ("TOP_START",    RectAlign::TOP_START),
+    ("TOP",          RectAlign::TOP),
+    ("TOP_END",      RectAlign::TOP_END),
+    ("RIGHT_START",  RectAlign::RIGHT_START),
+    ("RIGHT",        RectAlign::RIGHT),
+    ("RIGHT_END",    RectAlign::RIGHT_END),
+    ("BOTTOM_START", RectAlign::BOTTOM_START),
+    ("BOTTOM",       RectAlign::BOTTOM),
+    ("BOTTOM_END",   RectAlign::BOTTOM_END),
+    ("LEFT_START",   RectAlign::LEFT_START),
+    ("LEFT",         RectAlign::LEFT),
+    ("LEFT_END",     RectAlign::LEFT_END),
+];
+
+pub fn tooltip_position_name(position: RectAlign) -> Option<&'static str> {
+    TOOLTIP_POSITIONS
+        .iter()
+        .find(|(_, align)| *align == position)
+        .map(|(name, _)| *name)
+}
+
+// === Style presets ===
+// Everything about a texicon's look except its image, text and tooltip.
+// Colours are palette roles, so a preset follows the current theme.
+// Override single fields with `TexiStyle { frame_width: 4.0, ..PRESET }`.
+// `None` leaves the texicon's own default in place.
+#[rustfmt::skip]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TexiStyle {
+    pub name:             &'static str,
+    pub show_text:        bool,
+    pub img_size:         Option<Vec2>,
+    pub img_scale_hov:    Option<f32>,
+    pub text_size:        Option<f32>,
+    pub img_text_gap:     Option<f32>,
+    pub sense:            Option<SenseArea>,
+    pub bkgnd_col:        StateShades,
+    pub img_tint_col:     StateShades,
+    pub text_col:         StateShades,
+    pub frame_col:        StateShades,
+    pub frame_size:       Option<Vec2>,
+    pub frame_width:      f32,
+    pub radius:           Option<u8>,
+    pub tooltip_gap:      f32,
+    pub tooltip_position: Option<RectAlign>,
+}
+
+impl TexiStyle {
+    // The same style with all sizes multiplied by `scale`, for menus that shrink to fit
+    pub fn scaled(&self, scale: f32) -> Self {
+        if scale == 1.0 {
+            return *self;
+        }
+        Self {
+            img_size: self.img_size.map(|size| size * scale),
+            text_size: self.text_size.map(|size| size * scale),
+            img_text_gap: self.img_text_gap.map(|gap| gap * scale),
+            frame_size: self.frame_size.map(|size| size * scale),
+            ..*self
+        }
+    }
+
+    pub fn apply(&self, texi: Texicon<'static>, text: &str, palette: &Palette) -> Texicon<'static> {
+        let mut texi = texi
+            .bkgnd_col(palette.shade(self.bkgnd_col.normal))
+            .bkgnd_col_sel(palette.shade(self.bkgnd_col.sel))
+            .bkgnd_col_hov(palette.shade(self.bkgnd_col.hov))
+            .img_tint_col(palette.shade(self.img_tint_col.normal))
+            .img_tint_col_sel(palette.shade(self.img_tint_col.sel))
+            .img_tint_col_hov(palette.shade(self.img_tint_col.hov))
+            .text_col(palette.shade(self.text_col.normal))
+            .text_col_sel(palette.shade(self.text_col.sel))
+            .text_col_hov(palette.shade(self.text_col.hov))
+            .frame_col(palette.shade(self.frame_col.normal))
+            .frame_col_sel(palette.shade(self.frame_col.sel))
+            .frame_col_hov(palette.shade(self.frame_col.hov))
+            .frame_width(self.frame_width)
+            .tooltip_gap(self.tooltip_gap);
+
+        if self.show_text {
+            texi = texi.text(text.to_owned());
+        }
+        if let Some(size) = self.img_size {
+            texi = texi.img_size(size);
+        }
+        if let Some(scale) = self.img_scale_hov {
+            texi = texi.img_scale_hov(scale);
+        }
+        if let Some(size) = self.text_size {
+            texi = texi.text_size(size);
+        }
+        if let Some(gap) = self.img_text_gap {
+            texi = texi.img_text_gap(gap);
+        }
+        if let Some(sense) = self.sense {
+            texi = texi.sense(sense.texi_sense());
+        }
+        if let Some(size) = self.frame_size {
+            texi = texi.frame_size(size);
+        }
+        if let Some(radius) = self.radius {
+            texi = texi.radius(radius);
+        }
+        if let Some(position) = self.tooltip_position {
+            texi = texi.tooltip_position(position);
+        }
+        texi
+    }
+
+    // Fix the colours part way between the states, for animated transitions.
+    // `sel` and `hov` run from 0 (not selected / hovered) to 1 (fully).
+    pub fn apply_blended(
+        &self,
+        texi: Texicon<'static>,
+        palette: &Palette,
+        sel: f32,
+        hov: f32,
+    ) -> Texicon<'static> {
+        let blend = |shades: StateShades| {
+            palette
+                .shade(shades.normal)
+                .lerp_to_gamma(palette.shade(shades.sel), sel)
+                .lerp_to_gamma(palette.shade(shades.hov), hov)
+        };
+        let (bkgnd, tint, text, frame) = (
+            blend(self.bkgnd_col),
+            blend(self.img_tint_col),
+            blend(self.text_col),
+            blend(self.frame_col),
+        );
+        texi.bkgnd_col(bkgnd)
+            .bkgnd_col_sel(bkgnd)
+            .bkgnd_col_hov(bkgnd)
+            .img_tint_col(tint)
+            .img_tint_col_sel(tint)
+            .img_tint_col_hov(tint)
+            .text_col(text)
+            .text_col_sel(text)
+            .text_col_hov(text)
+            .frame_col(frame)
+            .frame_col_sel(frame)
+            .frame_col_hov(frame)
+    }
+}
@@ -0,0 +1,97 @@
+use egui::{vec2, Align2};
+
+use crate::palette::Palette;
+
+// === Constants ===
+#[rustfmt::skip] const TOAST_SECONDS: f64 = 5.0;
+#[rustfmt::skip] const TOAST_WIDTH: f32   = 320.0;
+#[rustfmt::skip] const TOAST_MARGIN: f32  = 12.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+struct Toast {
+    kind: ToastKind,
+    text: String,
+    // `InputState::time` when it goes away
+    until: f64,
+}
+
+// ------
+// Toasts
+// ------
+// Short messages in the bottom right corner that go away by themselves, or when clicked
+#[derive(Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn info(&mut self, ctx: &egui::Context, text: impl Into<String>) {
+        self.push(ctx, ToastKind::Info, text.into());
+    }
+
+    pub fn error(&mut self, ctx: &egui::Context, text: impl Into<String>) {
+        self.push(ctx, ToastKind::Error, text.into());
+    }
+
+    fn push(&mut self, ctx: &egui::Context, kind: ToastKind, text: String) {
+        let until = ctx.input(|i| i.time) + TOAST_SECONDS;
+        self.toasts.push(Toast { kind, text, until });
+    }
+
+    // The toasts showing, oldest first
+    pub fn messages(&self) -> impl Iterator<Item = (ToastKind, &str)> {
+        self.toasts
+            .iter()
+            .map(|toast| (toast.kind, toast.text.as_str()))
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        self.toasts.retain(|toast| toast.until > now);
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let palette = Palette::current(ctx);
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(Align2::RIGHT_BOTTOM, vec2(-TOAST_MARGIN, -TOAST_MARGIN))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(TOAST_WIDTH);
+                for (idx, toast) in self.toasts.iter().enumerate() {
+                    let color = match toast.kind {
+                        ToastKind::Info => palette.green,
+                        ToastKind::Error => palette.red,
+                    };
+                    let frame =
+                        egui::Frame::popup(ui.style()).stroke(egui::Stroke::new(1.0, color));
+                    let resp = frame
+                        .show(ui, |ui| {
+                            ui.colored_label(color, &toast.text);
+                        })
+                        .response
+                        .interact(egui::Sense::click());
+                    if resp.clicked() {
+                        dismissed = Some(idx);
+                    }
+                }
+            });
+        if let Some(idx) = dismissed {
+            self.toasts.remove(idx);
+        }
+
+        // Wake up to take the next one away
+        let next = self
+            .toasts
+            .iter()
+            .map(|toast| toast.until - now)
+            .fold(TOAST_SECONDS, f64::min);
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(next.max(0.0)));
+    }
+}
@@ -0,0 +1,149 @@
+// Walks the AccessKit tree of the demo app and checks the menus are exposed
+// as tab lists / radio groups with labelled, described items.
+
+mod common;
+
+use std::collections::HashMap;
+
+use common::Harness;
+use egui::accesskit::{Node, NodeId, Role, Toggled, TreeUpdate};
+
+fn accesskit_tree() -> TreeUpdate {
+    let mut harness = Harness::app(None);
+    harness.ctx.enable_accesskit();
+
+    // The first pass after enabling only lays things out, so take the tree from the second one
+    harness.frame(Vec::new());
+    harness
+        .output(Vec::new())
+        .platform_output
+        .accesskit_update
+        .expect("AccessKit is enabled, so every pass has a tree update")
+}
+
+struct Tree {
+    nodes: HashMap<NodeId, Node>,
+}
+
+impl Tree {
+    fn find_group(&self, role: Role, label: &str) -> &Node {
+        self.nodes
+            .values()
+            .find(|node| node.role() == role && node.label() == Some(label))
+            .unwrap_or_else(|| panic!("no {role:?} labelled {label:?}"))
+    }
+
+    // Descendants with the given role, in tree order, not looking inside matches
+    fn descendants<'a>(&'a self, node: &'a Node, role: Role) -> Vec<&'a Node> {
+        let mut found = Vec::new();
+        for id in node.children() {
+            let Some(child) = self.nodes.get(id) else {
+                continue;
+            };
+            if child.role() == role {
+                found.push(child);
+            } else {
+                found.extend(self.descendants(child, role));
+            }
+        }
+        found
+    }
+}
+
+fn tree() -> Tree {
+    Tree {
+        nodes: accesskit_tree().nodes.into_iter().collect(),
+    }
+}
+
+fn labels<'a>(nodes: &[&'a Node]) -> Vec<&'a str> {
+    nodes
+        .iter()
+        .map(|node| node.label().unwrap_or_default())
+        .collect()
+}
+
+#[test]
+fn side_menu_is_a_tab_list() {
+    let tree = tree();
+    let menu = tree.find_group(Role::TabList, "Side menu");
+    let tabs = tree.descendants(menu, Role::Tab);
+
+    assert_eq!(
+        labels(&tabs),
+        [
+            "Experiments",
+            "Timing Stuff",
+            "Filtering",
+            "Playground",
+            "Icon gallery",
+            "Theme editor",
+            "Settings"
+        ],
+        "side menu tabs"
+    );
+    let selected: Vec<bool> = tabs
+        .iter()
+        .map(|tab| tab.is_selected() == Some(true))
+        .collect();
+    assert_eq!(
+        selected,
+        [true, false, false, false, false, false, false],
+        "first tab is selected"
+    );
+    assert_eq!(
+        tabs.first().and_then(|tab| tab.description()),
+        Some("Text wrapping and centering for long words."),
+        "tooltip is the description"
+    );
+}
+
+#[test]
+fn top_menu_is_a_tab_list() {
+    let tree = tree();
+    let menu = tree.find_group(Role::TabList, "Top menu");
+    let tabs = tree.descendants(menu, Role::Tab);
+
+    assert_eq!(
+        labels(&tabs),
+        [
+            "Undersized text",
+            "Normal text",
+            "Large text",
+            "Extra large text"
+        ],
+        "top menu tabs"
+    );
+}
+
+#[test]
+fn central_menu_is_a_radio_group() {
+    let tree = tree();
+    let menu = tree.find_group(Role::RadioGroup, "Central menu");
+    let buttons = tree.descendants(menu, Role::RadioButton);
+
+    assert_eq!(buttons.len(), 5, "one radio button per texicon");
+    // Items without visible text are still labelled for screen readers
+    assert_eq!(
+        labels(&buttons).first().copied(),
+        Some("Experiments"),
+        "first radio button label"
+    );
+
+    let toggled: Vec<bool> = buttons
+        .iter()
+        .map(|button| button.toggled() == Some(Toggled::True))
+        .collect();
+    assert_eq!(
+        toggled,
+        [true, false, false, false, false],
+        "first option is checked"
+    );
+
+    let disabled: Vec<bool> = buttons.iter().map(|button| button.is_disabled()).collect();
+    assert_eq!(
+        disabled,
+        [false, false, false, false, true],
+        "only the documents texicon is disabled"
+    );
+}
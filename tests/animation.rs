@@ -0,0 +1,195 @@
+// Animated transitions: eased progress over the configured duration, and none with
+// reduce motion.
+
+mod common;
+
+use std::collections::HashSet;
+
+use common::{rect, Harness};
+use egui::Color32;
+use egui_widget_texicon_demo_app::animation::{AnimationSettings, Easing};
+use egui_widget_texicon_demo_app::menu_file::Menus;
+use egui_widget_texicon_demo_app::palette::{Palette, Role};
+use egui_widget_texicon_demo_app::texi_menu::{MyTexicon, TexiMenu};
+use egui_widget_texicon_demo_app::texi_side_menu;
+
+// === Constants ===
+#[rustfmt::skip] const DURATION: f32     = 0.2;
+// Frames to wait for the images to load
+#[rustfmt::skip] const MAX_FRAMES: usize = 50;
+
+// Runs `f` in a frame at `time` seconds
+fn at<T>(ctx: &egui::Context, time: f64, f: impl FnOnce(&egui::Context) -> T) -> T {
+    let raw_input = egui::RawInput {
+        time: Some(time),
+        ..Default::default()
+    };
+    let mut result = None;
+    let _output: egui::FullOutput = ctx.run_ui(raw_input, |ui| result = Some(f(ui.ctx())));
+    result.expect("the frame ran")
+}
+
+// How wide the image inside `item` is drawn in the next frame
+fn image_width(harness: &mut Harness<TexiMenu>, item: egui::Rect) -> f32 {
+    let output = harness.output(Vec::new());
+    let primitives = harness
+        .ctx
+        .tessellate(output.shapes, output.pixels_per_point);
+    primitives
+        .iter()
+        .filter_map(|clipped| match &clipped.primitive {
+            egui::epaint::Primitive::Mesh(mesh) => Some(mesh),
+            egui::epaint::Primitive::Callback(_) => None,
+        })
+        // Anything but the font atlas is an image
+        .filter(|mesh| mesh.texture_id != egui::TextureId::default())
+        .flat_map(|mesh| &mesh.vertices)
+        .filter(|vertex| item.contains(vertex.pos))
+        .fold(egui::Rect::NOTHING, |bounds, vertex| {
+            bounds.union(egui::Rect::from_pos(vertex.pos))
+        })
+        .width()
+}
+
+fn settings(easing: Easing) -> AnimationSettings {
+    AnimationSettings {
+        reduce_motion: false,
+        duration: DURATION,
+        easing,
+    }
+}
+
+#[test]
+fn blends_ease_towards_the_new_state() {
+    let ctx = egui::Context::default();
+    let id = egui::Id::new("hover");
+    let linear = settings(Easing::Linear);
+
+    assert_eq!(
+        at(&ctx, 0.0, |ctx| linear.blend(ctx, id, false)),
+        0.0,
+        "starts off"
+    );
+    let halfway = at(&ctx, 0.1, |ctx| linear.blend(ctx, id, true));
+    assert!(
+        halfway < 0.1,
+        "the first frame of a transition barely moves: {halfway}"
+    );
+    let halfway = at(&ctx, 0.2, |ctx| linear.blend(ctx, id, true));
+    assert!(halfway > 0.2 && halfway < 0.8, "part way there: {halfway}");
+    assert_eq!(
+        at(&ctx, 1.0, |ctx| linear.blend(ctx, id, true)),
+        1.0,
+        "arrived"
+    );
+
+    assert_eq!(Easing::EaseOut.apply(0.0), 0.0, "easing keeps the start");
+    assert_eq!(Easing::EaseOut.apply(1.0), 1.0, "easing keeps the end");
+    assert!(
+        Easing::EaseOut.apply(0.5) > 0.5,
+        "ease out is ahead of linear"
+    );
+    assert!(Easing::EaseIn.apply(0.5) < 0.5, "ease in is behind linear");
+}
+
+#[test]
+fn reduce_motion_snaps() {
+    let ctx = egui::Context::default();
+    let id = egui::Id::new("hover");
+    let reduced = AnimationSettings {
+        reduce_motion: true,
+        ..settings(Easing::EaseOut)
+    };
+    assert_eq!(
+        at(&ctx, 0.0, |ctx| reduced.blend(ctx, id, false)),
+        0.0,
+        "off"
+    );
+    assert_eq!(
+        at(&ctx, 0.01, |ctx| reduced.blend(ctx, id, true)),
+        1.0,
+        "on at once"
+    );
+
+    let mut target = Palette::current(&ctx);
+    target.set(Role::Base, Color32::from_rgb(1, 2, 3));
+    assert_eq!(reduced.palette(&ctx, target), target, "no theme fade");
+}
+
+#[test]
+fn theme_switches_fade() {
+    let ctx = egui::Context::default();
+    let linear = settings(Easing::Linear);
+    let old = Palette::current(&ctx);
+    let mut new = old;
+    new.set(Role::Base, Color32::from_rgb(255, 0, 0));
+
+    assert_eq!(
+        at(&ctx, 0.0, |ctx| linear.palette(ctx, old)),
+        old,
+        "no fade at first"
+    );
+    assert_eq!(
+        at(&ctx, 0.1, |ctx| linear.palette(ctx, new)),
+        old,
+        "the fade starts"
+    );
+    let fading = at(&ctx, 0.2, |ctx| linear.palette(ctx, new));
+    assert!(
+        fading.base != old.base && fading.base != new.base,
+        "part way: {:?}",
+        fading.base
+    );
+    assert_eq!(fading.mauve, new.mauve, "unchanged roles stay put");
+    assert_eq!(
+        at(&ctx, 1.0, |ctx| linear.palette(ctx, new)),
+        new,
+        "faded in"
+    );
+}
+
+#[test]
+fn texicons_animate_under_their_own_id() {
+    let menus = Menus::builtin();
+    for items in [&menus.top, &menus.side, &menus.central] {
+        let ids: HashSet<egui::Id> = items.iter().map(MyTexicon::stable_id).collect();
+        assert_eq!(ids.len(), items.len(), "one id per texicon");
+    }
+
+    // Texicons that weren't loaded from the menus fall back to their action, then text
+    let loose = |texicon: &MyTexicon| MyTexicon {
+        origin: None,
+        ..texicon.clone()
+    };
+    let ids: HashSet<egui::Id> = menus
+        .side
+        .iter()
+        .map(|texicon| loose(texicon).stable_id())
+        .collect();
+    assert_eq!(ids.len(), menus.side.len(), "still one id per texicon");
+}
+
+#[test]
+fn hover_grows_images_without_a_size() {
+    // The side menu leaves the image size to the texicon
+    let mut harness = Harness::menu(texi_side_menu::new_menu(Menus::builtin().side));
+    settings(Easing::Linear).set(&harness.ctx);
+    for _ in 0..MAX_FRAMES {
+        if !harness.ctx.has_pending_images() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        harness.frame(Vec::new());
+    }
+
+    let item = rect(&harness.drawn, 0);
+    let still = image_width(&mut harness, item);
+    harness.hover(item.center(), f64::from(DURATION) / 2.0);
+    let growing = image_width(&mut harness, item);
+    harness.hover(item.center(), 1.0);
+    let grown = image_width(&mut harness, item);
+    assert!(
+        still < growing && growing < grown,
+        "part way through the hover scale: {still} < {growing} < {grown}"
+    );
+}
@@ -0,0 +1,45 @@
+// The auto theme follows the system theme egui reports, as it changes.
+
+use egui::Theme;
+use egui_widget_texicon_demo_app::auto_theme::{AutoTheme, DarkVariant};
+use egui_widget_texicon_demo_app::palette::Palette;
+
+// Run a frame with the OS in `system` mode. Returns whether the theme switched.
+fn follow(ctx: &egui::Context, auto: AutoTheme, system: Theme) -> bool {
+    let raw_input = egui::RawInput {
+        system_theme: Some(system),
+        ..Default::default()
+    };
+    let mut switched = false;
+    let _output: egui::FullOutput = ctx.run_ui(raw_input, |ui| switched = auto.follow(ui.ctx()));
+    switched
+}
+
+#[test]
+fn follows_the_system_theme() {
+    let ctx = egui::Context::default();
+    let auto = AutoTheme {
+        enabled: true,
+        dark: DarkVariant::Frappe,
+    };
+
+    assert!(
+        follow(&ctx, auto, Theme::Light),
+        "light mode switches to Latte"
+    );
+    assert!(!follow(&ctx, auto, Theme::Light), "already Latte");
+    let latte = Palette::current(&ctx);
+
+    assert!(
+        follow(&ctx, auto, Theme::Dark),
+        "dark mode switches to Frappe"
+    );
+    assert!(!follow(&ctx, auto, Theme::Dark), "already Frappe");
+    assert_ne!(Palette::current(&ctx), latte, "the palette changed");
+
+    let manual = AutoTheme {
+        enabled: false,
+        ..auto
+    };
+    assert!(!follow(&ctx, manual, Theme::Light), "off, so it stays dark");
+}
@@ -0,0 +1,99 @@
+// Badges on texicons: what they show, menu files that set them, and the timer demo.
+
+use egui_widget_texicon_demo_app::action::Action;
+use egui_widget_texicon_demo_app::badge::{Badge, BadgeContent, BadgeCorner};
+use egui_widget_texicon_demo_app::badge_demo::BadgeDemo;
+use egui_widget_texicon_demo_app::menu_file::Menus;
+use egui_widget_texicon_demo_app::palette::Role;
+use egui_widget_texicon_demo_app::texi_side_menu;
+
+#[test]
+fn counts_are_capped() {
+    assert_eq!(Badge::count(7).label().as_deref(), Some("7"), "small count");
+    assert_eq!(
+        Badge::count(99).label().as_deref(),
+        Some("99"),
+        "at the cap"
+    );
+    assert_eq!(
+        Badge::count(100).label().as_deref(),
+        Some("99+"),
+        "past the cap"
+    );
+    assert_eq!(
+        Badge::count(10).max_count(9).label().as_deref(),
+        Some("9+"),
+        "a lower cap"
+    );
+    assert!(!Badge::count(0).is_visible(), "zero hides the badge");
+    assert!(Badge::dot().is_visible(), "dots always show");
+    assert_eq!(Badge::dot().label(), None, "dots have no text");
+    assert!(!Badge::text("").is_visible(), "empty text hides the badge");
+}
+
+#[test]
+fn menu_files_set_badges() {
+    let source = r#"(
+        top: [],
+        side: [
+            (icon: Builtin("clock"), text: "Inbox", badge: (content: Count(3))),
+            (
+                icon: Builtin("gear"),
+                text: "Updates",
+                badge: (content: Text("new"), corner: TopLeft, color: Blue, max_count: 9),
+            ),
+            (icon: Builtin("waves"), text: "Plain"),
+        ],
+        central: [],
+    )"#;
+    let menus = Menus::from_ron(source, "badges.ron", None).expect("the menu file loads");
+    let badges: Vec<Option<Badge>> = menus.side.into_iter().map(|item| item.badge).collect();
+    assert_eq!(
+        badges,
+        [
+            Some(Badge::count(3)),
+            Some(
+                Badge::new(BadgeContent::Text("new".to_owned()))
+                    .corner(BadgeCorner::TopLeft)
+                    .color(Role::Blue)
+                    .max_count(9)
+            ),
+            None,
+        ],
+        "badges as written, with the defaults filled in"
+    );
+
+    let bad = source.replace("Count(3)", "Count(-3)");
+    assert!(
+        Menus::from_ron(&bad, "badges.ron", None).is_err(),
+        "counts can't be negative"
+    );
+}
+
+#[test]
+fn the_demo_counts_on_the_timing_texicon() {
+    let ctx = egui::Context::default();
+    let mut side_menu = texi_side_menu::new_menu(Menus::builtin().side);
+    let timing = side_menu
+        .position_of(Action::Timing)
+        .expect("the side menu has the timing page");
+    let mut demo = BadgeDemo::default();
+
+    for time in [0.0, 0.5, 1.2, 3.1] {
+        let raw_input = egui::RawInput {
+            time: Some(time),
+            ..Default::default()
+        };
+        let _output: egui::FullOutput = ctx.run_ui(raw_input, |ui| demo.tick(ui.ctx()));
+    }
+    demo.apply(&mut side_menu);
+    assert_eq!(
+        side_menu.badge(timing),
+        Some(&Badge::count(3)),
+        "one count per second"
+    );
+
+    demo.enabled = false;
+    demo.apply(&mut side_menu);
+    assert_eq!(side_menu.badge(timing), None, "switched off");
+}
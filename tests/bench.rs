@@ -0,0 +1,114 @@
+// Frame time statistics over the rolling window, and their CSV and JSON exports.
+
+use egui_widget_texicon_demo_app::bench::{to_csv, to_json, FrameTimes, Stats};
+
+fn frame_times(capacity: usize, samples: impl IntoIterator<Item = f64>) -> FrameTimes {
+    let mut times = FrameTimes::new(capacity);
+    for micros in samples {
+        times.push(micros);
+    }
+    times
+}
+
+#[test]
+fn stats_of_a_known_sample() {
+    // 1..=100 µs, pushed out of order
+    let times = frame_times(1000, (1..=100).rev().map(f64::from));
+    assert_eq!(
+        times.stats(),
+        Some(Stats {
+            frames: 100,
+            min: 1.0,
+            max: 100.0,
+            mean: 50.5,
+            p50: 50.0,
+            p95: 95.0,
+            p99: 99.0,
+        }),
+        "nearest rank percentiles"
+    );
+
+    let single = frame_times(1000, [7.0]);
+    let stats = single.stats().expect("one frame");
+    assert_eq!(
+        [stats.min, stats.p50, stats.p99, stats.max],
+        [7.0; 4],
+        "every statistic of one frame is that frame"
+    );
+    assert_eq!(FrameTimes::new(10).stats(), None, "nothing to go on");
+}
+
+#[test]
+fn the_window_wraps_around() {
+    let mut times = frame_times(3, [1.0, 2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(
+        times.samples().collect::<Vec<_>>(),
+        [3.0, 4.0, 5.0],
+        "only the last frames, oldest first"
+    );
+    assert_eq!(times.latest(), Some(5.0), "latest");
+    assert_eq!(times.total_count(), 5, "totals count every frame");
+    assert_eq!(times.total_mean(), 3.0, "mean of every frame");
+    assert_eq!(
+        times.stats().map(|stats| (stats.frames, stats.min)),
+        Some((3, 3.0)),
+        "stats cover the window only"
+    );
+
+    times.clear();
+    assert_eq!(times.samples().len(), 0, "cleared");
+    assert_eq!(times.total_count(), 0, "totals too");
+
+    let tiny = frame_times(0, [1.0, 2.0]);
+    assert_eq!(
+        tiny.samples().collect::<Vec<_>>(),
+        [2.0],
+        "keeps at least one frame"
+    );
+}
+
+#[test]
+fn csv_has_a_row_per_frame() {
+    let top = frame_times(10, [1.0, 12.34]);
+    let side = frame_times(10, []);
+    assert_eq!(
+        to_csv(&[("Top menu", &top), ("Side menu", &side)]),
+        "menu,frame,micros\nTop menu,0,1.0\nTop menu,1,12.3\n",
+        "header, then the frames of each menu"
+    );
+}
+
+#[test]
+fn json_has_stats_and_samples_per_menu() {
+    let top = frame_times(10, [1.0, 3.0]);
+    let side = frame_times(10, []);
+    let json: serde_json::Value =
+        serde_json::from_str(&to_json(&[("Top menu", &top), ("Side menu", &side)]))
+            .expect("valid JSON");
+
+    let expected = serde_json::json!([
+        {
+            "menu": "Top menu",
+            "total_frames": 2,
+            "total_mean": 2.0,
+            "window": {
+                "frames": 2,
+                "min": 1.0,
+                "max": 3.0,
+                "mean": 2.0,
+                "p50": 1.0,
+                "p95": 3.0,
+                "p99": 3.0
+            },
+            "samples": [1.0, 3.0]
+        },
+        {
+            "menu": "Side menu",
+            "total_frames": 0,
+            "total_mean": 0.0,
+            "window": null,
+            "samples": []
+        }
+    ]);
+    assert_eq!(json, expected, "one object per menu");
+}
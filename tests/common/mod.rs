@@ -0,0 +1,236 @@
+// Helpers shared by the integration tests
+// Each test crate uses only some of them
+#![allow(dead_code)]
+
+pub mod raster;
+
+use std::collections::HashMap;
+
+use egui_widget_texicon_demo_app::action::{TexiEvent, TexiEventKind};
+use egui_widget_texicon_demo_app::texi_menu::TexiMenu;
+use egui_widget_texicon_demo_app::TexiconDemoApp;
+
+// === Constants ===
+#[rustfmt::skip] pub const FRAME_TIME: f64    = 1.0 / 60.0;
+#[rustfmt::skip] pub const SCREEN: egui::Vec2 = egui::vec2(1400.0, 900.0);
+// Longer than egui's double-click delay
+#[rustfmt::skip] pub const PAUSE: f64         = 1.0;
+// Pointer moves per drag, so egui sees it as one
+#[rustfmt::skip] pub const DRAG_STEPS: u16    = 5;
+
+// -------
+// Storage
+// -------
+// eframe storage kept in memory
+#[derive(Default)]
+pub struct MemoryStorage(HashMap<String, String>);
+
+impl eframe::Storage for MemoryStorage {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+    fn set_string(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_owned(), value);
+    }
+    fn flush(&mut self) {}
+}
+
+// ----
+// Draw
+// ----
+// Anything drawn in a frame: a menu on its own, or the whole app
+pub trait Draw {
+    fn draw(&mut self, ui: &mut egui::Ui) -> Vec<TexiEvent>;
+}
+
+impl Draw for TexiMenu {
+    fn draw(&mut self, ui: &mut egui::Ui) -> Vec<TexiEvent> {
+        let mut events = Vec::new();
+        egui::CentralPanel::default().show(ui, |ui| events = self.draw_texicons(ui));
+        events
+    }
+}
+
+impl Draw for TexiconDemoApp {
+    fn draw(&mut self, ui: &mut egui::Ui) -> Vec<TexiEvent> {
+        self.show(ui);
+        Vec::new()
+    }
+}
+
+// -------
+// Harness
+// -------
+// Runs frames headless on a 1400×900 screen, FRAME_TIME apart, with synthetic input
+pub struct Harness<T> {
+    pub ctx: egui::Context,
+    pub drawn: T,
+    pub time: f64,
+    // Held down during the next frames
+    pub modifiers: egui::Modifiers,
+    // Everything emitted so far
+    pub events: Vec<TexiEvent>,
+}
+
+impl Harness<TexiconDemoApp> {
+    pub fn app(storage: Option<&dyn eframe::Storage>) -> Self {
+        Self::new(|ctx| TexiconDemoApp::from_storage(ctx, storage))
+    }
+}
+
+impl Harness<TexiMenu> {
+    pub fn menu(menu: TexiMenu) -> Self {
+        Self::new(|_ctx| menu)
+    }
+}
+
+impl<T: Draw> Harness<T> {
+    pub fn new(make: impl FnOnce(&egui::Context) -> T) -> Self {
+        let ctx = egui::Context::default();
+        egui_extras::install_image_loaders(&ctx);
+        let drawn = make(&ctx);
+        let mut harness = Self {
+            ctx,
+            drawn,
+            time: 0.0,
+            modifiers: egui::Modifiers::NONE,
+            events: Vec::new(),
+        };
+        // Lay everything out once, so the texicons have rects
+        harness.frame(Vec::new());
+        harness.frame(Vec::new());
+        harness
+    }
+
+    pub fn output(&mut self, events: Vec<egui::Event>) -> egui::FullOutput {
+        let raw_input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, SCREEN)),
+            time: Some(self.time),
+            modifiers: self.modifiers,
+            events,
+            ..Default::default()
+        };
+        let (drawn, emitted) = (&mut self.drawn, &mut self.events);
+        let output = self
+            .ctx
+            .run_ui(raw_input, |ui| emitted.extend(drawn.draw(ui)));
+        self.time += FRAME_TIME;
+        output
+    }
+
+    pub fn frame(&mut self, events: Vec<egui::Event>) {
+        let _output: egui::FullOutput = self.output(events);
+    }
+
+    // Press and release `button` at `pos`
+    pub fn press(&mut self, pos: egui::Pos2, button: egui::PointerButton) {
+        let modifiers = self.modifiers;
+        let event = |pressed| egui::Event::PointerButton {
+            pos,
+            button,
+            pressed,
+            modifiers,
+        };
+        self.frame(vec![egui::Event::PointerMoved(pos), event(true)]);
+        self.frame(vec![event(false)]);
+    }
+
+    pub fn click(&mut self, pos: egui::Pos2) {
+        self.click_with(pos, egui::Modifiers::NONE);
+    }
+
+    // A primary click with `modifiers` held, and a frame to react to it
+    pub fn click_with(&mut self, pos: egui::Pos2, modifiers: egui::Modifiers) {
+        self.modifiers = modifiers;
+        self.press(pos, egui::PointerButton::Primary);
+        self.modifiers = egui::Modifiers::NONE;
+        self.frame(Vec::new());
+    }
+
+    pub fn key(&mut self, key: egui::Key) {
+        self.key_with(key, egui::Modifiers::NONE);
+    }
+
+    // Press and release `key` with `modifiers` held
+    pub fn key_with(&mut self, key: egui::Key, modifiers: egui::Modifiers) {
+        let event = |pressed| egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers,
+        };
+        self.modifiers = modifiers;
+        self.frame(vec![event(true)]);
+        self.modifiers = egui::Modifiers::NONE;
+        self.frame(vec![event(false)]);
+    }
+
+    // Press at `from`, move to `to` in a few steps and let go there
+    pub fn drag(&mut self, from: egui::Pos2, to: egui::Pos2) {
+        let button = |pos, pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: egui::Modifiers::NONE,
+        };
+        self.frame(vec![egui::Event::PointerMoved(from), button(from, true)]);
+        for step in 1..=DRAG_STEPS {
+            let pos = from.lerp(to, f32::from(step) / f32::from(DRAG_STEPS));
+            self.frame(vec![egui::Event::PointerMoved(pos)]);
+        }
+        self.frame(vec![button(to, false)]);
+        self.frame(Vec::new());
+    }
+
+    // Keep the pointer still over `pos` for `seconds`
+    pub fn hover(&mut self, pos: egui::Pos2, seconds: f64) {
+        self.frame(vec![egui::Event::PointerMoved(pos)]);
+        let end = self.time + seconds;
+        while self.time < end {
+            self.frame(Vec::new());
+        }
+    }
+
+    // Let the double-click delay pass
+    pub fn pause(&mut self) {
+        self.time += PAUSE;
+        self.frame(Vec::new());
+    }
+
+    // The rect of the tooltip on screen, if one is showing
+    pub fn tooltip_rect(&self) -> Option<egui::Rect> {
+        self.ctx.memory(|mem| {
+            mem.areas()
+                .visible_layer_ids()
+                .into_iter()
+                .filter(|layer| layer.order == egui::Order::Tooltip)
+                .find_map(|layer| mem.area_rect(layer.id))
+        })
+    }
+
+    // The kinds of the events emitted since the last call, hovering aside
+    pub fn take_kinds(&mut self) -> Vec<TexiEventKind> {
+        self.events
+            .drain(..)
+            .map(|event| event.kind)
+            .filter(|kind| !matches!(kind, TexiEventKind::HoverEntered | TexiEventKind::HoverLeft))
+            .collect()
+    }
+}
+
+// -----
+// Menus
+// -----
+pub fn rect(menu: &TexiMenu, idx: usize) -> egui::Rect {
+    menu.item_rect(idx)
+        .unwrap_or_else(|| panic!("texicon {idx} of the {} was drawn", menu.name()))
+}
+
+pub fn center(menu: &TexiMenu, idx: usize) -> egui::Pos2 {
+    rect(menu, idx).center()
+}
+
+pub fn selected(menu: &TexiMenu) -> Vec<usize> {
+    menu.selected_indices().collect()
+}
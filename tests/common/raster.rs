@@ -0,0 +1,194 @@
+// A small CPU rasteriser for egui's tessellated output, so snapshots need no GPU.
+// Pixels are premultiplied sRGB, blended in gamma space like egui's own renderers.
+
+use std::collections::HashMap;
+
+use egui::epaint::{ClippedPrimitive, Primitive, Vertex};
+use egui::{Color32, ColorImage, TextureId, TexturesDelta};
+
+// Every texture egui has uploaded so far (font atlas and images)
+#[derive(Default)]
+pub struct Textures {
+    images: HashMap<TextureId, ColorImage>,
+}
+
+impl Textures {
+    // Apply the uploads and frees of one frame
+    pub fn update(&mut self, delta: &TexturesDelta) {
+        for (id, image_delta) in &delta.set {
+            let egui::ImageData::Color(image) = &image_delta.image;
+            match image_delta.pos {
+                None => {
+                    self.images.insert(*id, (**image).clone());
+                }
+                Some([x0, y0]) => {
+                    let Some(target) = self.images.get_mut(id) else {
+                        continue;
+                    };
+                    for y in 0..image.height() {
+                        for x in 0..image.width() {
+                            let src = image.pixels.get(y * image.width() + x);
+                            let dst = target.pixels.get_mut((y0 + y) * target.width() + x0 + x);
+                            if let (Some(src), Some(dst)) = (src, dst) {
+                                *dst = *src;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        for id in &delta.free {
+            self.images.remove(id);
+        }
+    }
+
+    fn sample(&self, id: TextureId, uv: egui::Pos2) -> Color32 {
+        let Some(image) = self.images.get(&id) else {
+            return Color32::WHITE;
+        };
+        let [w, h] = image.size;
+        let x = ((uv.x * w as f32) as usize).min(w.saturating_sub(1));
+        let y = ((uv.y * h as f32) as usize).min(h.saturating_sub(1));
+        image
+            .pixels
+            .get(y * w + x)
+            .copied()
+            .unwrap_or(Color32::WHITE)
+    }
+}
+
+// Draw `primitives` onto an opaque `background`. Returns RGBA8 rows, top first.
+pub fn rasterize(
+    primitives: &[ClippedPrimitive],
+    textures: &Textures,
+    size: [u32; 2],
+    pixels_per_point: f32,
+    background: Color32,
+) -> image::RgbaImage {
+    let [width, height] = size;
+    let mut pixels = vec![background; (width * height) as usize];
+
+    for clipped in primitives {
+        let Primitive::Mesh(mesh) = &clipped.primitive else {
+            continue;
+        };
+        let clip = clipped.clip_rect * pixels_per_point;
+        for tri in mesh.indices.chunks_exact(3) {
+            let vertex = |i: usize| {
+                tri.get(i)
+                    .and_then(|&idx| mesh.vertices.get(idx as usize))
+                    .copied()
+            };
+            let (Some(a), Some(b), Some(c)) = (vertex(0), vertex(1), vertex(2)) else {
+                continue;
+            };
+            let scale = |v: Vertex| Vertex {
+                pos: (v.pos.to_vec2() * pixels_per_point).to_pos2(),
+                ..v
+            };
+            fill_triangle(
+                &mut pixels,
+                [width, height],
+                clip,
+                [scale(a), scale(b), scale(c)],
+                |uv| textures.sample(mesh.texture_id, uv),
+            );
+        }
+    }
+
+    let bytes = pixels.iter().flat_map(|px| px.to_array()).collect();
+    image::RgbaImage::from_raw(width, height, bytes).expect("buffer matches the image size")
+}
+
+fn fill_triangle(
+    pixels: &mut [Color32],
+    [width, height]: [u32; 2],
+    clip: egui::Rect,
+    [a, b, c]: [Vertex; 3],
+    sample: impl Fn(egui::Pos2) -> Color32,
+) {
+    let area = edge(a.pos, b.pos, c.pos);
+    if area.abs() < f32::EPSILON {
+        return;
+    }
+    let min_x = a.pos.x.min(b.pos.x).min(c.pos.x).max(clip.min.x).max(0.0);
+    let max_x = a
+        .pos
+        .x
+        .max(b.pos.x)
+        .max(c.pos.x)
+        .min(clip.max.x)
+        .min(width as f32);
+    let min_y = a.pos.y.min(b.pos.y).min(c.pos.y).max(clip.min.y).max(0.0);
+    let max_y = a
+        .pos
+        .y
+        .max(b.pos.y)
+        .max(c.pos.y)
+        .min(clip.max.y)
+        .min(height as f32);
+
+    for y in (min_y.floor() as u32)..(max_y.ceil() as u32) {
+        for x in (min_x.floor() as u32)..(max_x.ceil() as u32) {
+            let p = egui::pos2(x as f32 + 0.5, y as f32 + 0.5);
+            if !clip.contains(p) {
+                continue;
+            }
+            // Barycentric weights; the sign check accepts either winding
+            let wa = edge(b.pos, c.pos, p) / area;
+            let wb = edge(c.pos, a.pos, p) / area;
+            let wc = edge(a.pos, b.pos, p) / area;
+            if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                continue;
+            }
+            let uv = (a.uv.to_vec2() * wa + b.uv.to_vec2() * wb + c.uv.to_vec2() * wc).to_pos2();
+            let color = lerp3(a.color, b.color, c.color, [wa, wb, wc]);
+            let src = multiply(sample(uv), color);
+            if let Some(dst) = pixels.get_mut((y * width + x) as usize) {
+                *dst = blend(src, *dst);
+            }
+        }
+    }
+}
+
+fn edge(a: egui::Pos2, b: egui::Pos2, p: egui::Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+fn lerp3(a: Color32, b: Color32, c: Color32, [wa, wb, wc]: [f32; 3]) -> Color32 {
+    let mix = |x: u8, y: u8, z: u8| {
+        (f32::from(x) * wa + f32::from(y) * wb + f32::from(z) * wc)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    };
+    let ([r1, g1, b1, a1], [r2, g2, b2, a2], [r3, g3, b3, a3]) =
+        (a.to_array(), b.to_array(), c.to_array());
+    Color32::from_rgba_premultiplied(
+        mix(r1, r2, r3),
+        mix(g1, g2, g3),
+        mix(b1, b2, b3),
+        mix(a1, a2, a3),
+    )
+}
+
+// Combine two colours channel by channel
+fn zip_channels(a: Color32, b: Color32, f: impl Fn(u16, u16) -> u16) -> Color32 {
+    let ([r1, g1, b1, a1], [r2, g2, b2, a2]) = (a.to_array(), b.to_array());
+    let channel = |x: u8, y: u8| f(u16::from(x), u16::from(y)).min(255) as u8;
+    Color32::from_rgba_premultiplied(
+        channel(r1, r2),
+        channel(g1, g2),
+        channel(b1, b2),
+        channel(a1, a2),
+    )
+}
+
+fn multiply(a: Color32, b: Color32) -> Color32 {
+    zip_channels(a, b, |x, y| (x * y + 127) / 255)
+}
+
+// Premultiplied "over"
+fn blend(src: Color32, dst: Color32) -> Color32 {
+    let keep = 255 - u16::from(src.a());
+    zip_channels(src, dst, |s, d| s + (d * keep + 127) / 255)
+}
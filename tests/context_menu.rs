@@ -0,0 +1,185 @@
+// Context menus in menu files, double- and middle-clicks as their own events,
+// and pinning texicons to the top menu.
+
+mod common;
+
+use common::{center, Harness};
+use egui_widget_texicon_demo_app::action::{Action, ContextAction, ContextEntry, TexiEventKind};
+use egui_widget_texicon_demo_app::menu_file::Menus;
+use egui_widget_texicon_demo_app::pages::PageId;
+use egui_widget_texicon_demo_app::texi_side_menu;
+
+// === Constants ===
+// Longer than toasts show
+#[rustfmt::skip] const TOAST_WAIT: f64 = 6.0;
+
+#[test]
+fn menu_files_set_context_menus() {
+    let source = r#"(
+        top: [],
+        side: [
+            (
+                icon: Builtin("clock"),
+                text: "Inbox",
+                context: [
+                    (text: "Open", action: Open),
+                    (text: "Pin", action: PinToTopMenu),
+                ],
+            ),
+            (icon: Builtin("waves"), text: "Plain"),
+        ],
+        central: [],
+    )"#;
+    let menus = Menus::from_ron(source, "context.ron", None).expect("the menu file loads");
+    let contexts: Vec<Vec<ContextEntry>> =
+        menus.side.into_iter().map(|item| item.context).collect();
+    assert_eq!(
+        contexts,
+        [
+            vec![
+                ContextEntry::new("Open", ContextAction::Open),
+                ContextEntry::new("Pin", ContextAction::PinToTopMenu),
+            ],
+            Vec::new(),
+        ],
+        "entries as written, none by default"
+    );
+
+    let bad = source.replace("PinToTopMenu", "Delete");
+    assert!(
+        Menus::from_ron(&bad, "context.ron", None).is_err(),
+        "only known actions"
+    );
+}
+
+#[test]
+fn double_and_middle_clicks_are_their_own_events() {
+    let mut harness = Harness::menu(texi_side_menu::new_menu(Menus::builtin().side));
+    let pos = center(&harness.drawn, 2);
+
+    harness.press(pos, egui::PointerButton::Primary);
+    harness.press(pos, egui::PointerButton::Primary);
+    assert_eq!(
+        harness.take_kinds(),
+        [
+            TexiEventKind::Activated,
+            TexiEventKind::Activated,
+            TexiEventKind::DoubleClicked
+        ],
+        "both clicks activate, then the double-click follows"
+    );
+    assert!(harness.drawn.is_selected(2), "selected");
+
+    harness.pause();
+    let other = center(&harness.drawn, 0);
+    harness.press(other, egui::PointerButton::Middle);
+    assert_eq!(
+        harness.take_kinds(),
+        [TexiEventKind::MiddleClicked],
+        "a middle-click"
+    );
+    assert!(
+        harness.drawn.is_selected(2),
+        "middle-clicks leave the selection alone"
+    );
+
+    harness.pause();
+    harness.press(other, egui::PointerButton::Secondary);
+    assert_eq!(
+        harness.take_kinds(),
+        [TexiEventKind::ContextRequested],
+        "a right-click"
+    );
+}
+
+#[test]
+fn double_clicks_pin_to_the_top_menu() {
+    let mut harness = Harness::app(None);
+    let top_count = harness.drawn.top_menu().item_count();
+    let timing = harness
+        .drawn
+        .side_menu()
+        .position_of(Action::Timing)
+        .expect("the side menu has the timing page");
+    let pos = center(harness.drawn.side_menu(), timing);
+
+    harness.press(pos, egui::PointerButton::Primary);
+    harness.press(pos, egui::PointerButton::Primary);
+    harness.frame(Vec::new());
+    let top = harness.drawn.top_menu();
+    assert_eq!(top.item_count(), top_count + 1, "pinned");
+    let pinned = top.position_of(Action::Timing).expect("pinned at the end");
+    assert_eq!(pinned, top_count, "pinned at the end");
+    let unpin = top.item(pinned).map(|item| item.context.last().cloned());
+    assert_eq!(
+        unpin,
+        Some(Some(ContextEntry::new("Unpin", ContextAction::Unpin))),
+        "pinned texicons can be unpinned"
+    );
+
+    harness.pause();
+    harness.press(pos, egui::PointerButton::Primary);
+    harness.press(pos, egui::PointerButton::Primary);
+    assert_eq!(
+        harness.drawn.top_menu().item_count(),
+        top_count + 1,
+        "pinned once only"
+    );
+
+    // Double-clicks on the top menu itself pin nothing. Wait for the toasts to go first.
+    harness.time += TOAST_WAIT;
+    harness.frame(Vec::new());
+    let first = center(harness.drawn.top_menu(), 0);
+    harness.press(first, egui::PointerButton::Primary);
+    harness.press(first, egui::PointerButton::Primary);
+    harness.frame(Vec::new());
+    assert_eq!(
+        harness.drawn.top_menu().item_count(),
+        top_count + 1,
+        "top menu texicons aren't pinned again"
+    );
+    assert_eq!(
+        harness.drawn.toasts().messages().count(),
+        0,
+        "not even tried"
+    );
+
+    // The pinned texicon leads to its page
+    harness.pause();
+    let experiments = harness
+        .drawn
+        .side_menu()
+        .position_of(Action::Experiments)
+        .expect("the side menu has the experiments page");
+    harness.press(
+        center(harness.drawn.side_menu(), experiments),
+        egui::PointerButton::Primary,
+    );
+    harness.pause();
+    harness.press(
+        center(harness.drawn.top_menu(), pinned),
+        egui::PointerButton::Primary,
+    );
+    harness.frame(Vec::new());
+    assert_eq!(
+        harness.drawn.current_page(),
+        PageId::Timing,
+        "followed the pin"
+    );
+}
+
+#[test]
+fn removing_items_keeps_the_selection_in_step() {
+    let mut menu = texi_side_menu::new_menu(Menus::builtin().side);
+    menu.set_selected_texicon(3);
+    let removed = menu.remove_item(1).map(|item| item.text);
+    assert_eq!(removed.as_deref(), Some("Timing Stuff"), "the second item");
+    assert_eq!(menu.selected_texicon(), Some(2), "the selection moved up");
+    assert!(menu.remove_item(99).is_none(), "out of range");
+
+    let pushed = menu.item(0).cloned().expect("the menu has items");
+    let count = menu.item_count();
+    menu.push_item(pushed);
+    assert_eq!(menu.item_count(), count + 1, "added at the end");
+    assert!(!menu.is_selected(count), "unselected");
+}
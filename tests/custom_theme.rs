@@ -0,0 +1,73 @@
+// Custom themes: the theme file format, and switching the menus' palette.
+
+use egui::Color32;
+use egui_widget_texicon_demo_app::custom_theme::{self, CustomTheme};
+use egui_widget_texicon_demo_app::palette::{Palette, Role};
+
+fn custom(ctx: &egui::Context) -> CustomTheme {
+    let mut palette = Palette::current(ctx);
+    palette.set(Role::Mauve, Color32::from_rgb(0x12, 0x34, 0x56));
+    palette.set(Role::Base, Color32::from_rgb(0xfe, 0xdc, 0xba));
+    CustomTheme::new("Sunrise", false, palette)
+}
+
+#[test]
+fn theme_files_round_trip() {
+    let theme = custom(&egui::Context::default());
+    let ron = theme.to_ron();
+    assert!(
+        ron.contains(r##"Mauve: "#123456""##),
+        "hex colours in:\n{ron}"
+    );
+    assert_eq!(CustomTheme::parse(&ron), Ok(theme), "RON:\n{ron}");
+}
+
+#[test]
+fn bad_theme_files_are_reported() {
+    let ron = custom(&egui::Context::default()).to_ron();
+    let missing: String = ron
+        .lines()
+        .filter(|line| !line.contains("Teal:"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let err = CustomTheme::parse(&missing).expect_err("a colour is missing");
+    assert!(err.contains("the theme has no teal colour"), "{err}");
+    assert!(
+        CustomTheme::parse(&ron.replace("#123456", "purple")).is_err(),
+        "not a hex colour"
+    );
+}
+
+#[test]
+fn the_active_theme_styles_the_menus() {
+    let ctx = egui::Context::default();
+    let catppuccin = Palette::current(&ctx);
+    let theme = custom(&ctx);
+
+    custom_theme::register(&ctx, theme.clone());
+    assert_eq!(
+        Palette::current(&ctx),
+        catppuccin,
+        "registering doesn't switch"
+    );
+
+    custom_theme::set_active(&ctx, true);
+    assert_eq!(
+        Palette::current(&ctx),
+        theme.palette,
+        "switched to the custom theme"
+    );
+
+    // Edits to the active theme show straight away
+    let mut edited = theme;
+    edited.palette.set(Role::Red, Color32::from_rgb(1, 2, 3));
+    custom_theme::register(&ctx, edited.clone());
+    assert_eq!(Palette::current(&ctx), edited.palette, "live edit");
+
+    custom_theme::set_active(&ctx, false);
+    assert_eq!(
+        Palette::current(&ctx),
+        catppuccin,
+        "back to the themenator theme"
+    );
+}
@@ -0,0 +1,203 @@
+// Drags texicons along their menus and from one menu to another, and checks the
+// resulting order survives a restart through eframe storage.
+
+mod common;
+
+use common::{rect, selected, Harness, MemoryStorage};
+use egui_widget_texicon_demo_app::menu_file::Menus;
+use egui_widget_texicon_demo_app::menu_layout::MenuLayout;
+use egui_widget_texicon_demo_app::texi_menu::{MyTexicon, TexiMenu};
+use egui_widget_texicon_demo_app::{
+    texi_central_menu, texi_side_menu, texi_top_menu, TexiconDemoApp,
+};
+
+// Just after texicon `idx` of a vertical menu: in the lower half of it
+fn below(menu: &TexiMenu, idx: usize) -> egui::Pos2 {
+    let rect = rect(menu, idx);
+    rect.center() + egui::vec2(0.0, rect.height() / 4.0)
+}
+
+fn texts(menu: &TexiMenu) -> Vec<&str> {
+    menu.items()
+        .iter()
+        .map(|texicon| texicon.text.as_str())
+        .collect()
+}
+
+#[test]
+fn dragging_along_a_menu_reorders_it() {
+    let mut harness = Harness::app(None);
+    let side = harness.drawn.side_menu();
+    assert!(side.is_selected(0), "starts on the first page");
+    let (from, to) = (rect(side, 0).center(), below(side, 2));
+    harness.drag(from, to);
+
+    let side = harness.drawn.side_menu();
+    assert_eq!(
+        texts(side).into_iter().take(3).collect::<Vec<_>>(),
+        ["Timing Stuff", "Filtering", "Experiments"],
+        "the first texicon moved below the third"
+    );
+    assert!(side.is_selected(2), "the selection moved with it");
+}
+
+#[test]
+fn dragging_onto_another_menu_pins_a_copy() {
+    let mut harness = Harness::app(None);
+    let central_before = harness.drawn.central_menu().item_count();
+    let side_before = harness.drawn.side_menu().item_count();
+    let from = rect(harness.drawn.central_menu(), 2).center();
+    let to = below(harness.drawn.side_menu(), 1);
+    harness.drag(from, to);
+
+    let side = harness.drawn.side_menu();
+    assert_eq!(side.item_count(), side_before + 1, "pinned");
+    assert_eq!(
+        texts(side).get(2).copied(),
+        Some("FILTERING"),
+        "where it was dropped"
+    );
+    assert_eq!(
+        harness.drawn.central_menu().item_count(),
+        central_before,
+        "the central menu keeps its texicon"
+    );
+
+    harness.drag(from, to);
+    assert_eq!(
+        harness.drawn.side_menu().item_count(),
+        side_before + 1,
+        "pinned once only"
+    );
+
+    // The central menu doesn't take texicons from other menus
+    let from = rect(harness.drawn.top_menu(), 0).center();
+    let to = rect(harness.drawn.central_menu(), 0).center();
+    harness.drag(from, to);
+    assert_eq!(
+        harness.drawn.central_menu().item_count(),
+        central_before,
+        "nothing dropped into the central menu"
+    );
+}
+
+#[test]
+fn the_layout_survives_a_restart() {
+    let mut storage = MemoryStorage::default();
+    let mut harness = Harness::app(None);
+    // The first texicon of the top menu to the right half of the last, after it
+    let top = harness.drawn.top_menu();
+    let last = rect(top, 3);
+    let (from, to) = (
+        rect(top, 0).center(),
+        last.center() + egui::vec2(last.width() / 4.0, 0.0),
+    );
+    harness.drag(from, to);
+    let from = rect(harness.drawn.central_menu(), 3).center();
+    let to = below(harness.drawn.side_menu(), 1);
+    harness.drag(from, to);
+    eframe::App::save(&mut harness.drawn, &mut storage);
+
+    let restarted = Harness::app(Some(&storage));
+    for (before, after) in [
+        (harness.drawn.top_menu(), restarted.drawn.top_menu()),
+        (harness.drawn.side_menu(), restarted.drawn.side_menu()),
+        (harness.drawn.central_menu(), restarted.drawn.central_menu()),
+    ] {
+        assert_eq!(texts(after), texts(before), "{}", before.name());
+    }
+    let context = |app: &TexiconDemoApp| {
+        app.side_menu()
+            .item(2)
+            .map(|texicon| texicon.context.clone())
+    };
+    assert_eq!(
+        context(&restarted.drawn),
+        context(&harness.drawn),
+        "still a pinned copy"
+    );
+}
+
+#[test]
+fn layouts_only_fit_the_menus_they_were_made_for() {
+    let menus = Menus::builtin();
+    let top = egui_widget_texicon_demo_app::texi_top_menu::new_menu(menus.top.clone());
+    let side = egui_widget_texicon_demo_app::texi_side_menu::new_menu(menus.side.clone());
+    let central = egui_widget_texicon_demo_app::texi_central_menu::new_menu(menus.central.clone());
+    let layout = MenuLayout::of(&top, &side, &central);
+
+    let items = layout.items(&menus).expect("the layout fits");
+    let side_texts: Vec<String> = items.side.into_iter().map(|texicon| texicon.text).collect();
+    assert_eq!(side_texts, texts(&side), "as loaded");
+
+    let mut renamed = Menus::builtin();
+    if let Some(texicon) = renamed.side.first_mut() {
+        texicon.text = "Renamed".to_owned();
+    }
+    assert!(
+        layout.items(&renamed).is_none(),
+        "a changed menu file drops the layout"
+    );
+}
+
+#[test]
+fn the_selection_survives_a_restart_after_unsaved_texicons() {
+    let menus = Menus::builtin();
+    let top = texi_top_menu::new_menu(menus.top.clone());
+    let central = texi_central_menu::new_menu(menus.central.clone());
+    // A texicon the layout leaves out, before the selected one
+    let mut side = texi_side_menu::new_menu(menus.side.clone());
+    let loose = menus.side.first().map(|texicon| MyTexicon {
+        text: "Not from the menus".to_owned(),
+        origin: None,
+        ..texicon.clone()
+    });
+    side.insert_item(0, loose.expect("the side menu has texicons"));
+    side.set_selected_texicon(2);
+    let was_selected = side.item_text(2).map(str::to_owned);
+
+    // What `TexiconDemoApp` saves, and restores on the next start
+    let layout = MenuLayout::of(&top, &side, &central);
+    let saved = MenuLayout::selected_index(&side);
+    let items = layout.items(&menus).expect("the layout fits");
+    let mut restarted = texi_side_menu::new_menu(items.side);
+    restarted.set_selected_texicon(saved);
+
+    assert_eq!(restarted.item_count(), side.item_count() - 1, "left out");
+    assert_eq!(
+        restarted
+            .selected_texicon()
+            .and_then(|idx| restarted.item_text(idx))
+            .map(str::to_owned),
+        was_selected,
+        "the same texicon is selected"
+    );
+
+    side.set_selected_texicon(0);
+    assert_eq!(
+        MenuLayout::selected_index(&side),
+        0,
+        "a selected texicon that isn't saved falls back to the first"
+    );
+}
+
+#[test]
+fn selections_past_the_saved_layout_fall_back_to_the_first_texicon() {
+    let mut storage = MemoryStorage::default();
+    let mut harness = Harness::app(None);
+    eframe::App::save(&mut harness.drawn, &mut storage);
+
+    // A side menu selection past the texicons of the saved layout
+    let state = eframe::Storage::get_string(&storage, eframe::APP_KEY).expect("the app saved");
+    let past = harness.drawn.side_menu().item_count();
+    let edited = state.replace("side_selected:0", &format!("side_selected:{past}"));
+    assert_ne!(edited, state, "the saved selection was edited");
+    eframe::Storage::set_string(&mut storage, eframe::APP_KEY, edited);
+
+    let restarted = Harness::app(Some(&storage));
+    assert_eq!(
+        selected(restarted.drawn.side_menu()),
+        [0],
+        "the first texicon is selected"
+    );
+}
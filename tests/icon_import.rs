@@ -0,0 +1,101 @@
+// Dropping SVG/PNG files on the app imports them as icons, and reports bad files.
+
+use std::sync::Arc;
+
+use egui_widget_texicon_demo_app::icon_import::{ImportError, MAX_ICON_BYTES};
+use egui_widget_texicon_demo_app::icon_registry::{IconOrigin, IconRegistry};
+use egui_widget_texicon_demo_app::toast::ToastKind;
+use egui_widget_texicon_demo_app::TexiconDemoApp;
+
+const SVG: &[u8] = br#"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="8"/>"#;
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n(rest of the file)";
+
+fn imported(icons: &IconRegistry) -> Vec<&str> {
+    icons
+        .entries()
+        .filter(|entry| entry.origin == IconOrigin::Imported)
+        .map(|entry| entry.name.as_str())
+        .collect()
+}
+
+fn dropped(name: &str, bytes: &[u8]) -> egui::DroppedFile {
+    egui::DroppedFile {
+        name: name.to_owned(),
+        bytes: Some(Arc::from(bytes)),
+        ..Default::default()
+    }
+}
+
+// One frame of the app with `files` dropped on it. Returns the app's toasts.
+fn drop_files(files: Vec<egui::DroppedFile>) -> (TexiconDemoApp, Vec<(ToastKind, String)>) {
+    let ctx = egui::Context::default();
+    egui_extras::install_image_loaders(&ctx);
+    let mut app = TexiconDemoApp::from_storage(&ctx, None);
+    let raw_input = egui::RawInput {
+        dropped_files: files,
+        ..Default::default()
+    };
+    let _output: egui::FullOutput = ctx.run_ui(raw_input, |ui| app.show(ui));
+    let toasts = app
+        .toasts()
+        .messages()
+        .map(|(kind, text)| (kind, text.to_owned()))
+        .collect();
+    (app, toasts)
+}
+
+#[test]
+fn dropped_icons_are_imported() {
+    let (app, toasts) = drop_files(vec![dropped("rocket.svg", SVG), dropped("Photo.PNG", PNG)]);
+
+    assert_eq!(
+        imported(app.icons()),
+        ["rocket.svg", "Photo.PNG"],
+        "both files became icons"
+    );
+    assert!(app.icons().icon("rocket.svg").is_some(), "found by name");
+    assert!(
+        app.icons().icon("testtube").is_some(),
+        "the built-in icons are still there"
+    );
+    assert!(
+        toasts.iter().all(|(kind, _)| *kind == ToastKind::Info),
+        "no errors: {toasts:?}"
+    );
+}
+
+#[test]
+fn bad_files_show_an_error() {
+    let too_large = vec![b' '; MAX_ICON_BYTES + 1];
+    let (app, toasts) = drop_files(vec![
+        dropped("notes.txt", b"<svg"),
+        dropped("fake.png", SVG),
+        dropped("huge.svg", &too_large),
+    ]);
+
+    assert!(imported(app.icons()).is_empty(), "nothing was imported");
+    let errors: Vec<&str> = toasts
+        .iter()
+        .filter(|(kind, _)| *kind == ToastKind::Error)
+        .map(|(_, text)| text.as_str())
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ImportError::Unsupported {
+                name: "notes.txt".to_owned()
+            }
+            .to_string(),
+            ImportError::Invalid {
+                name: "fake.png".to_owned()
+            }
+            .to_string(),
+            ImportError::TooLarge {
+                name: "huge.svg".to_owned(),
+                bytes: MAX_ICON_BYTES + 1
+            }
+            .to_string(),
+        ],
+        "one error toast per file"
+    );
+}
@@ -0,0 +1,84 @@
+// The icon registry: built-in icons, scanned directories, tags and search.
+
+use egui_widget_texicon_demo_app::icon_registry::{IconOrigin, IconRegistry};
+use egui_widget_texicon_demo_app::menu_file::BUILTIN_ICONS;
+
+const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="8"/>"#;
+
+fn found<'a>(icons: &'a IconRegistry, query: &'a str) -> Vec<&'a str> {
+    icons
+        .search(query)
+        .map(|entry| entry.name.as_str())
+        .collect()
+}
+
+#[test]
+fn builtin_icons_are_tagged() {
+    let icons = IconRegistry::default();
+    assert_eq!(
+        icons.names().count(),
+        BUILTIN_ICONS.len(),
+        "every built-in icon is registered"
+    );
+    let gear = icons
+        .entries()
+        .find(|entry| entry.name == "gear-light")
+        .expect("the light gear is built in");
+    assert_eq!(gear.origin, IconOrigin::Builtin, "origin");
+    assert_eq!(
+        gear.tags,
+        ["config", "gear", "light", "settings", "svg"],
+        "name words, file type and the built-in tags, sorted"
+    );
+}
+
+#[test]
+fn search_matches_names_and_tags() {
+    let icons = IconRegistry::default();
+    assert_eq!(found(&icons, ""), icons.names().collect::<Vec<_>>(), "all");
+    assert_eq!(
+        found(&icons, "GEAR"),
+        ["gear", "gear-light"],
+        "ignores case"
+    );
+    assert_eq!(
+        found(&icons, "settings light"),
+        ["gear-light"],
+        "every word"
+    );
+    assert_eq!(found(&icons, "png"), ["article"], "file type");
+    assert!(found(&icons, "nothing-like-it").is_empty(), "no match");
+}
+
+#[test]
+fn scanning_a_directory_adds_its_icons() {
+    let dir = std::env::temp_dir().join(format!("texicon-icons-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    std::fs::write(dir.join("rocket_launch.svg"), SVG).expect("write svg");
+    std::fs::write(dir.join("broken.png"), "not a png").expect("write png");
+    std::fs::write(dir.join("readme.txt"), "skipped").expect("write txt");
+    std::fs::write(dir.join("tags.ron"), r#"{"rocket_launch.svg": ["Space"]}"#).expect("tags");
+
+    let ctx = egui::Context::default();
+    let mut icons = IconRegistry::default();
+    let revision = icons.revision();
+    let errors = icons.scan_dir(&ctx, &dir);
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert_eq!(errors.len(), 1, "only the broken png fails: {errors:?}");
+    let rocket = icons
+        .entries()
+        .find(|entry| entry.name == "rocket_launch.svg")
+        .expect("the svg was added");
+    assert_eq!(rocket.origin, IconOrigin::Directory, "origin");
+    assert_eq!(
+        rocket.tags,
+        ["launch", "rocket", "space", "svg"],
+        "tagged from the name and tags.ron"
+    );
+    assert!(
+        icons.icon("readme.txt").is_none(),
+        "other files are skipped"
+    );
+    assert_ne!(icons.revision(), revision, "the revision changed");
+}
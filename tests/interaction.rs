@@ -0,0 +1,148 @@
+// Drives the demo app headless with synthetic pointer events and checks
+// selection and tooltips behave as the demo promises.
+
+mod common;
+
+use common::{center, selected, Harness, MemoryStorage};
+use egui_widget_texicon_demo_app::pages::PageId;
+use egui_widget_texicon_demo_app::texi_central_menu::GHOST;
+use egui_widget_texicon_demo_app::texi_menu::TexiMenu;
+use egui_widget_texicon_demo_app::TexiconDemoApp;
+
+// === Constants ===
+// Long enough for egui's tooltip delay
+#[rustfmt::skip] const HOVER_TIME: f64   = 1.5;
+// Tooltip placement may be off by rounding and frame strokes
+#[rustfmt::skip] const TOLERANCE: f32    = 2.0;
+// The "Documents" texicon of the central menu, which is disabled
+#[rustfmt::skip] const DOCUMENTS: usize  = 4;
+// The "Ghost" texicon of the central menu
+#[rustfmt::skip] const GHOST_ITEM: usize = 1;
+
+#[test]
+fn clicking_a_texicon_selects_only_it() {
+    let mut harness = Harness::app(None);
+    let menus: [fn(&TexiconDemoApp) -> &TexiMenu; 3] = [
+        TexiconDemoApp::top_menu,
+        TexiconDemoApp::central_menu,
+        // Last, as it changes the page
+        TexiconDemoApp::side_menu,
+    ];
+
+    for menu_of in menus {
+        let count = menu_of(&harness.drawn).item_count();
+        for idx in 0..count {
+            let menu = menu_of(&harness.drawn);
+            if idx == DOCUMENTS && menu.name() == "Central menu" {
+                continue;
+            }
+            let pos = center(menu, idx);
+            harness.click(pos);
+
+            let menu = menu_of(&harness.drawn);
+            assert_eq!(
+                selected(menu),
+                [idx],
+                "{}: clicking texicon {idx} selects only it",
+                menu.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn clicking_a_disabled_texicon_keeps_the_selection() {
+    let mut harness = Harness::app(None);
+    let before = selected(harness.drawn.central_menu());
+
+    let pos = center(harness.drawn.central_menu(), DOCUMENTS);
+    harness.click(pos);
+
+    assert_eq!(
+        selected(harness.drawn.central_menu()),
+        before,
+        "the disabled Documents texicon can't be selected"
+    );
+}
+
+#[test]
+fn side_menu_chooses_the_page() {
+    let mut harness = Harness::app(None);
+    assert_eq!(
+        harness.drawn.current_page(),
+        PageId::Experiments,
+        "starts on the first page"
+    );
+
+    for (idx, page) in PageId::ALL.into_iter().enumerate().rev() {
+        let pos = center(harness.drawn.side_menu(), idx);
+        harness.click(pos);
+        assert_eq!(
+            harness.drawn.current_page(),
+            page,
+            "side menu texicon {idx}"
+        );
+    }
+}
+
+#[test]
+fn the_page_survives_a_restart() {
+    let mut storage = MemoryStorage::default();
+    let mut harness = Harness::app(None);
+    let pos = center(harness.drawn.side_menu(), 1);
+    harness.click(pos);
+    assert_eq!(harness.drawn.current_page(), PageId::Timing, "clicked");
+    eframe::App::save(&mut harness.drawn, &mut storage);
+
+    let restarted = Harness::app(Some(&storage));
+    assert_eq!(
+        restarted.drawn.current_page(),
+        PageId::Timing,
+        "back on the saved page"
+    );
+    assert!(
+        restarted.drawn.side_menu().is_selected(1),
+        "and its texicon is selected"
+    );
+}
+
+#[test]
+fn page_slugs_round_trip() {
+    for page in PageId::ALL {
+        assert_eq!(PageId::from_slug(page.slug()), Some(page), "{page:?}");
+        assert_eq!(
+            PageId::from_slug(&format!("#{}", page.slug())),
+            Some(page),
+            "{page:?} with a leading #"
+        );
+    }
+    assert_eq!(PageId::from_slug("#nowhere"), None, "unknown page");
+}
+
+#[test]
+fn hovering_shows_a_tooltip_where_configured() {
+    let mut harness = Harness::app(None);
+    assert_eq!(harness.tooltip_rect(), None, "no tooltip before hovering");
+
+    let item = harness
+        .drawn
+        .central_menu()
+        .item_rect(GHOST_ITEM)
+        .expect("the ghost texicon was drawn");
+    harness.hover(item.center(), HOVER_TIME);
+
+    let tooltip = harness
+        .tooltip_rect()
+        .expect("hovering a texicon shows its tooltip");
+    let position = GHOST
+        .tooltip_position
+        .expect("the ghost preset places its tooltip");
+    let expected = position.align_rect(&item, tooltip.size(), GHOST.tooltip_gap);
+    assert!(
+        (tooltip.min - expected.min).length() <= TOLERANCE,
+        "tooltip at {:?}, expected {:?} ({position:?} with a gap of {})",
+        tooltip.min,
+        expected.min,
+        GHOST.tooltip_gap
+    );
+}
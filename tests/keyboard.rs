@@ -0,0 +1,194 @@
+// Drives the menus with the keyboard alone: arrow keys, Home/End, Enter/Space and Escape
+// inside a menu, Tab from menu to menu and on to the page, and text fields keeping their keys.
+
+mod common;
+
+use common::{selected, Draw, Harness};
+use egui::{Key, Modifiers};
+use egui_widget_texicon_demo_app::action::{TexiEvent, TexiEventKind};
+use egui_widget_texicon_demo_app::menu_file::Menus;
+use egui_widget_texicon_demo_app::texi_menu::TexiMenu;
+use egui_widget_texicon_demo_app::{
+    texi_central_menu, texi_side_menu, texi_top_menu, TexiconDemoApp,
+};
+
+// === Constants ===
+// The "Documents" texicon of the central menu, which is disabled
+#[rustfmt::skip] const DOCUMENTS: usize = 4;
+// The app's menus, in Tab order
+#[rustfmt::skip] const TOP: usize       = 0;
+#[rustfmt::skip] const SIDE: usize      = 1;
+#[rustfmt::skip] const CENTRAL: usize   = 2;
+
+// A menu with a text field above it
+struct WithTextField {
+    menu: TexiMenu,
+    text: String,
+    field: Option<egui::Rect>,
+}
+
+impl Draw for WithTextField {
+    fn draw(&mut self, ui: &mut egui::Ui) -> Vec<TexiEvent> {
+        let mut events = Vec::new();
+        egui::CentralPanel::default().show(ui, |ui| {
+            self.field = Some(ui.text_edit_singleline(&mut self.text).rect);
+            events = self.menu.draw_texicons(ui);
+        });
+        events
+    }
+}
+
+// Which of the app's menus has the keyboard focus
+fn focused_menu(app: &TexiconDemoApp) -> Option<usize> {
+    [app.top_menu(), app.side_menu(), app.central_menu()]
+        .into_iter()
+        .position(TexiMenu::has_keyboard_focus)
+}
+
+#[test]
+fn arrows_home_and_end_move_the_focus_ring() {
+    let mut harness = Harness::menu(texi_side_menu::new_menu(Menus::builtin().side));
+    let last = harness.drawn.item_count() - 1;
+    harness.drawn.set_keyboard_focus(true);
+    assert_eq!(
+        harness.drawn.focused_item(),
+        Some(0),
+        "on the selected texicon"
+    );
+
+    let moves = [
+        (Key::ArrowDown, 1),
+        (Key::ArrowDown, 2),
+        (Key::ArrowUp, 1),
+        (Key::End, last),
+        (Key::ArrowDown, last),
+        (Key::Home, 0),
+        (Key::ArrowUp, 0),
+    ];
+    for (key, expected) in moves {
+        harness.key(key);
+        assert_eq!(
+            harness.drawn.focused_item(),
+            Some(expected),
+            "after {key:?}"
+        );
+    }
+    assert_eq!(selected(&harness.drawn), [0], "moving doesn't select");
+
+    // A horizontal menu goes left and right instead
+    let mut harness = Harness::menu(texi_top_menu::new_menu(Menus::builtin().top));
+    harness.drawn.set_keyboard_focus(true);
+    harness.key(Key::ArrowDown);
+    assert_eq!(harness.drawn.focused_item(), Some(0), "not down");
+    harness.key(Key::ArrowRight);
+    assert_eq!(harness.drawn.focused_item(), Some(1), "but right");
+}
+
+#[test]
+fn enter_and_space_activate_the_focused_texicon() {
+    let mut harness = Harness::menu(texi_central_menu::new_menu(Menus::builtin().central));
+    harness.drawn.set_keyboard_focus(true);
+    harness.events.clear();
+
+    harness.key(Key::ArrowRight);
+    harness.key(Key::Enter);
+    assert_eq!(selected(&harness.drawn), [1], "Enter selects");
+    harness.key(Key::ArrowRight);
+    harness.key(Key::Space);
+    assert_eq!(selected(&harness.drawn), [2], "and so does Space");
+    assert_eq!(
+        harness.take_kinds(),
+        [TexiEventKind::Activated, TexiEventKind::Activated],
+        "activated like a click"
+    );
+
+    harness.key(Key::End);
+    assert_eq!(
+        harness.drawn.focused_item(),
+        Some(DOCUMENTS),
+        "on the disabled texicon"
+    );
+    harness.key(Key::Enter);
+    assert_eq!(
+        selected(&harness.drawn),
+        [2],
+        "disabled texicons can't be selected"
+    );
+    assert!(harness.take_kinds().is_empty(), "nor activated");
+
+    harness.key(Key::Escape);
+    assert_eq!(
+        harness.drawn.focused_item(),
+        None,
+        "Escape gives up the focus"
+    );
+    harness.key(Key::Home);
+    harness.key(Key::Enter);
+    assert_eq!(selected(&harness.drawn), [2], "keys are ignored after that");
+}
+
+#[test]
+fn text_fields_keep_their_keys() {
+    let mut harness = Harness::new(|_ctx| WithTextField {
+        menu: texi_side_menu::new_menu(Menus::builtin().side),
+        text: String::new(),
+        field: None,
+    });
+    harness.drawn.menu.set_keyboard_focus(true);
+    let field = harness.drawn.field.expect("the text field was drawn");
+    harness.click(field.center());
+
+    harness.key(Key::ArrowDown);
+    harness.key(Key::Enter);
+    assert_eq!(
+        harness.drawn.menu.focused_item(),
+        None,
+        "the text field took the keyboard"
+    );
+    assert_eq!(
+        selected(&harness.drawn.menu),
+        [0],
+        "the menu saw none of the keys"
+    );
+}
+
+#[test]
+fn tab_goes_through_the_menus_and_on_to_the_page() {
+    let mut harness = Harness::app(None);
+    assert_eq!(focused_menu(&harness.drawn), None, "no focus at first");
+
+    for expected in [TOP, SIDE, CENTRAL] {
+        harness.key(Key::Tab);
+        assert_eq!(focused_menu(&harness.drawn), Some(expected), "Tab");
+    }
+    harness.key_with(Key::Tab, Modifiers::SHIFT);
+    assert_eq!(
+        focused_menu(&harness.drawn),
+        Some(SIDE),
+        "Shift+Tab goes back"
+    );
+    harness.key(Key::Tab);
+
+    // Past the last menu, egui moves the focus on to the page's widgets
+    harness.key(Key::Tab);
+    assert_eq!(focused_menu(&harness.drawn), None, "out of the menus");
+    let focus = harness.ctx.memory(|mem| mem.focused());
+    let app = &harness.drawn;
+    let on_texicon = |id| {
+        [app.top_menu(), app.side_menu(), app.central_menu()]
+            .into_iter()
+            .any(|menu| menu.item_with_id(id).is_some())
+    };
+    assert!(
+        focus.is_some_and(|id| !on_texicon(id)),
+        "a widget of the page has the focus"
+    );
+
+    let before = selected(harness.drawn.side_menu());
+    harness.key(Key::Enter);
+    assert_eq!(
+        selected(harness.drawn.side_menu()),
+        before,
+        "the menus leave its keys alone"
+    );
+}
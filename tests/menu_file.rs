@@ -0,0 +1,128 @@
+// Menu files that can't be used are reported with the file, line and item,
+// and file icons are found next to the menu file wherever the app runs from.
+
+use egui::ImageSource;
+use egui_widget_texicon_demo_app::menu_file::{MenuError, Menus};
+
+// A menu file with `item` as the second texicon of the side menu
+fn with_side_item(item: &str) -> String {
+    format!(
+        r#"#![enable(implicit_some)]
+(
+    top: [],
+    side: [
+        (icon: Builtin("clock"), text: "First"),
+        {item},
+    ],
+    central: [],
+)"#
+    )
+}
+
+fn load_error(source: &str) -> MenuError {
+    match Menus::from_ron(source, "menus.ron", None) {
+        Ok(_) => panic!("the menu file loaded:\n{source}"),
+        Err(err) => err,
+    }
+}
+
+#[test]
+fn malformed_ron_names_the_line() {
+    let err = load_error(&with_side_item(r#"(icon: Builtin("clock"), text: )"#));
+    assert!(
+        matches!(err, MenuError::Parse { .. }),
+        "a parse error: {err:?}"
+    );
+    assert!(
+        err.to_string().starts_with("menus.ron:6:"),
+        "file and line of the bad item: {err}"
+    );
+}
+
+#[test]
+fn unknown_builtin_icons_name_the_item() {
+    let err = load_error(&with_side_item(
+        r#"(icon: Builtin("rocket"), text: "Second")"#,
+    ));
+    assert!(
+        matches!(
+            err,
+            MenuError::Item {
+                menu: "side",
+                index: 1,
+                ..
+            }
+        ),
+        "the second side item: {err:?}"
+    );
+    assert!(
+        err.to_string().starts_with(
+            r#"menus.ron: side menu, item 1 ("Second"): unknown built-in icon "rocket" (expected one of: testtube, clock,"#
+        ),
+        "what's wrong and what would do: {err}"
+    );
+}
+
+#[test]
+fn bad_styles_name_the_item() {
+    let err = load_error(&with_side_item(
+        r#"(icon: Builtin("clock"), text: "Second", style: (text_size: -4.0))"#,
+    ));
+    assert_eq!(
+        err.to_string(),
+        r#"menus.ron: side menu, item 1 ("Second"): style.text_size must be finite and not negative, got -4"#,
+        "the field and its value"
+    );
+
+    let err = load_error(&with_side_item(
+        r#"(icon: Builtin("clock"), text: "Second", style: (img_size: (32.0, -1.0)))"#,
+    ));
+    assert_eq!(
+        err.to_string(),
+        r#"menus.ron: side menu, item 1 ("Second"): style.img_size must be finite and not negative, got (32, -1)"#,
+        "sizes too"
+    );
+}
+
+#[test]
+fn file_icons_are_relative_to_the_menu_file() {
+    let dir = std::env::temp_dir().join(format!("texicon-menus-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("pics")).expect("temp dir");
+    std::fs::write(
+        dir.join("pics").join("dot.svg"),
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="8"/>"#,
+    )
+    .expect("write svg");
+    let good = with_side_item(r#"(icon: File("pics/dot.svg"), text: "Second")"#);
+    std::fs::write(dir.join("menus.ron"), &good).expect("write menus");
+    std::fs::write(dir.join("missing.ron"), good.replace("dot.svg", "gone.svg"))
+        .expect("write menus");
+
+    let loaded = Menus::from_file(&dir.join("menus.ron"));
+    let missing = Menus::from_file(&dir.join("missing.ron")).err();
+    let expected =
+        std::fs::canonicalize(dir.join("pics").join("dot.svg")).expect("the icon exists");
+    std::fs::remove_dir_all(&dir).ok();
+
+    let menus = loaded.expect("the menu file loads");
+    let uri = match menus.side.get(1).map(|item| &item.img) {
+        Some(ImageSource::Uri(uri)) => uri.to_string(),
+        _ => panic!("a file icon is loaded by URI"),
+    };
+    // On Windows without the `\\?\` prefix of canonical paths, and with forward slashes
+    let expected = expected.display().to_string();
+    #[cfg(windows)]
+    let expected = expected
+        .strip_prefix(r"\\?\")
+        .unwrap_or(&expected)
+        .replace('\\', "/");
+    assert_eq!(
+        uri,
+        format!("file://{expected}"),
+        "an absolute URI, whatever the working directory"
+    );
+    assert!(
+        missing.is_some_and(|err| err.to_string().contains("gone.svg not found")),
+        "missing icon files are reported"
+    );
+}
@@ -0,0 +1,154 @@
+// The playground's exports: Rust builder code, and RON/JSON that loads back.
+
+use egui::{vec2, Color32, RectAlign};
+use egui_widget_texicon_demo_app::icon_registry::{IconOrigin, IconRegistry};
+use egui_widget_texicon_demo_app::menu_file::file_uri;
+use egui_widget_texicon_demo_app::page_playground::{Paint, PlaygroundTexicon};
+use egui_widget_texicon_demo_app::palette::{Palette, Role};
+use egui_widget_texicon_demo_app::texi_export;
+use egui_widget_texicon_demo_app::texi_style::SenseArea;
+
+// A texicon with every kind of value changed from the defaults
+fn tuned(palette: &Palette) -> PlaygroundTexicon {
+    let mut texicon = PlaygroundTexicon {
+        icon: "article".to_owned(),
+        text: "Tuned \"texicon\"".to_owned(),
+        img_size: Some(vec2(52.5, 48.)),
+        text_size: None,
+        sense: Some(SenseArea::ImageAndText),
+        frame_size: None,
+        radius: Some(3),
+        tooltip_position: Some(RectAlign::LEFT_END),
+        enabled: false,
+        ..PlaygroundTexicon::default()
+    };
+    texicon.bkgnd_col.hov = Paint::Custom(Color32::from_rgba_premultiplied(10, 20, 30, 40));
+    // A fixed colour that happens to be a palette entry
+    texicon.frame_col.sel = Paint::Custom(palette.get(Role::Mauve));
+    texicon
+}
+
+#[test]
+fn ron_and_json_round_trip() {
+    let palette = Palette::current(&egui::Context::default());
+    for texicon in [PlaygroundTexicon::default(), tuned(&palette)] {
+        let ron = texi_export::to_ron(&texicon);
+        assert_eq!(texi_export::parse(&ron), Ok(texicon.clone()), "RON:\n{ron}");
+
+        let json = texi_export::to_json(&texicon);
+        assert_eq!(
+            texi_export::parse(&json),
+            Ok(texicon.clone()),
+            "JSON:\n{json}"
+        );
+    }
+}
+
+#[test]
+fn missing_fields_take_the_defaults() {
+    let texicon =
+        texi_export::parse(r#"(text: "Short", radius: Some(4))"#).expect("a partial texicon loads");
+    assert_eq!(
+        texicon,
+        PlaygroundTexicon {
+            text: "Short".to_owned(),
+            radius: Some(4),
+            ..PlaygroundTexicon::default()
+        },
+        "only the given fields change"
+    );
+}
+
+#[test]
+fn bad_input_is_reported() {
+    assert!(
+        texi_export::parse(r#"(tooltip_position: Some("MIDDLE"))"#).is_err(),
+        "unknown tooltip position"
+    );
+    assert!(
+        texi_export::parse(r#"{"radius": "round"}"#).is_err(),
+        "wrong type in JSON"
+    );
+    assert!(
+        texi_export::parse("(colour: 3)").is_err(),
+        "unknown field in RON"
+    );
+}
+
+#[test]
+fn rust_export_uses_palette_roles() {
+    let palette = Palette::current(&egui::Context::default());
+    let rust = texi_export::to_rust(&tuned(&palette), &palette, &IconRegistry::default());
+
+    for expected in [
+        "let palette = egui_widget_themenator::ThemeVariant::get_current_palette(ui.ctx());",
+        r#"Texicon::new(include_image!("../assets/pics/article.png"))"#,
+        ".enabled(false)",
+        ".bkgnd_col(palette.base)",
+        ".bkgnd_col_hov(Color32::from_rgba_premultiplied(10, 20, 30, 40))",
+        ".frame_col_sel(palette.mauve)",
+        r#".text("Tuned \"texicon\"")"#,
+        ".img_size(vec2(52.5, 48.0))",
+        ".sense(TexiSense::ImageAndText)",
+        ".radius(3)",
+        ".tooltip_position(RectAlign::LEFT_END)",
+    ] {
+        assert!(rust.contains(expected), "{expected} in:\n{rust}");
+    }
+    for unexpected in [".selected(", ".text_size(", ".frame_size("] {
+        assert!(!rust.contains(unexpected), "no {unexpected} in:\n{rust}");
+    }
+}
+
+#[test]
+fn rust_export_loads_other_icons_from_their_files() {
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="8"/>"#;
+    let dir = std::env::temp_dir().join(format!("texicon-export-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    std::fs::write(dir.join("rocket.svg"), SVG).expect("write svg");
+
+    let ctx = egui::Context::default();
+    let palette = Palette::current(&ctx);
+    let mut icons = IconRegistry::default();
+    let errors = icons.scan_dir(&ctx, &dir);
+    let file = std::fs::canonicalize(dir.join("rocket.svg")).expect("the svg exists");
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(errors.is_empty(), "{errors:?}");
+    let dropped = SVG.as_bytes().to_vec();
+    icons
+        .add_file(
+            &ctx,
+            "dropped.svg",
+            dropped,
+            IconOrigin::Imported,
+            &[],
+            None,
+        )
+        .expect("a valid svg");
+
+    let export = |icon: &str| {
+        let texicon = PlaygroundTexicon {
+            icon: icon.to_owned(),
+            ..PlaygroundTexicon::default()
+        };
+        texi_export::to_rust(&texicon, &palette, &icons)
+    };
+
+    let rust = export("rocket.svg");
+    let uri = file_uri(&file);
+    let expected = format!("Texicon::new(egui::ImageSource::Uri({uri:?}.into()))");
+    assert!(rust.contains(&expected), "{expected} in:\n{rust}");
+    assert!(!rust.contains("TODO"), "nothing left to do in:\n{rust}");
+
+    let rust = export("dropped.svg");
+    for expected in [
+        r#"// TODO: add "dropped.svg""#,
+        r#"Texicon::new(egui::ImageSource::Uri("file://dropped.svg".into()))"#,
+    ] {
+        assert!(rust.contains(expected), "{expected} in:\n{rust}");
+    }
+    assert!(
+        !rust.contains("include_image!"),
+        "only built-in icons are included in:\n{rust}"
+    );
+}
@@ -0,0 +1,124 @@
+// Clicks through a menu in each selection mode and checks which texicons end up selected.
+
+mod common;
+
+use common::{center, selected, Harness};
+use egui_widget_texicon_demo_app::action::{Action, TexiEvent, TexiEventKind};
+use egui_widget_texicon_demo_app::menu_file::Menus;
+use egui_widget_texicon_demo_app::texi_menu::{SelectionMode, TexiMenu};
+
+fn top_menu(mode: SelectionMode) -> Harness<TexiMenu> {
+    Harness::menu(
+        egui_widget_texicon_demo_app::texi_top_menu::new_menu(Menus::builtin().top).selection(mode),
+    )
+}
+
+fn click_with(harness: &mut Harness<TexiMenu>, idx: usize, modifiers: egui::Modifiers) {
+    let pos = center(&harness.drawn, idx);
+    harness.click_with(pos, modifiers);
+}
+
+fn click(harness: &mut Harness<TexiMenu>, idx: usize) {
+    click_with(harness, idx, egui::Modifiers::NONE);
+}
+
+#[test]
+fn single() {
+    let mut harness = top_menu(SelectionMode::Single);
+    click(&mut harness, 1);
+    click(&mut harness, 1);
+    assert_eq!(
+        selected(&harness.drawn),
+        [1],
+        "clicking again keeps it selected"
+    );
+    click(&mut harness, 2);
+    assert_eq!(selected(&harness.drawn), [2], "only one is selected");
+}
+
+#[test]
+fn single_with_deselect() {
+    let mut harness = top_menu(SelectionMode::SingleWithDeselect);
+    click(&mut harness, 1);
+    assert_eq!(selected(&harness.drawn), [1], "first click selects");
+    click(&mut harness, 1);
+    assert!(
+        selected(&harness.drawn).is_empty(),
+        "second click deselects"
+    );
+}
+
+#[test]
+fn multiple_toggle() {
+    let mut harness = top_menu(SelectionMode::MultipleToggle);
+    click(&mut harness, 0);
+    click(&mut harness, 2);
+    assert_eq!(
+        selected(&harness.drawn),
+        [0, 2],
+        "clicks add to the selection"
+    );
+    click(&mut harness, 0);
+    assert_eq!(selected(&harness.drawn), [2], "clicking again toggles off");
+}
+
+#[test]
+fn multiple_ranges() {
+    let mut harness = top_menu(SelectionMode::MultipleToggle);
+    click(&mut harness, 0);
+    click_with(&mut harness, 2, egui::Modifiers::SHIFT);
+    assert_eq!(
+        selected(&harness.drawn),
+        [0, 1, 2],
+        "shift-click selects a range"
+    );
+
+    click(&mut harness, 3);
+    click_with(&mut harness, 3, egui::Modifiers::SHIFT);
+    assert_eq!(
+        selected(&harness.drawn),
+        [3],
+        "shift-click replaces the selection"
+    );
+
+    click(&mut harness, 0);
+    click_with(
+        &mut harness,
+        1,
+        egui::Modifiers::SHIFT | egui::Modifiers::COMMAND,
+    );
+    assert_eq!(
+        selected(&harness.drawn),
+        [0, 1, 3],
+        "ctrl+shift-click adds a range"
+    );
+}
+
+#[test]
+fn none() {
+    let mut harness = top_menu(SelectionMode::None);
+    click(&mut harness, 1);
+    assert!(
+        selected(&harness.drawn).is_empty(),
+        "action buttons never select"
+    );
+}
+
+#[test]
+fn events() {
+    let mut harness = top_menu(SelectionMode::None);
+    click(&mut harness, 1);
+    let event = |kind| TexiEvent {
+        kind,
+        item: 1,
+        action: Some(Action::TextNormal),
+    };
+    assert_eq!(
+        harness.events,
+        [
+            event(TexiEventKind::HoverEntered),
+            event(TexiEventKind::Activated)
+        ],
+        "hovering then clicking, even with nothing to select"
+    );
+}
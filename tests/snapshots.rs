@@ -0,0 +1,219 @@
+// Golden image tests: every menu in every theme and state, rendered headless,
+// rasterised on the CPU and compared with the PNGs in `tests/snapshots/`.
+//
+// A missing snapshot fails the test. Record new ones, or re-record them all after an
+// intended visual change, with `UPDATE_SNAPSHOTS=1 cargo test`, and commit them.
+
+mod common;
+
+use std::path::PathBuf;
+
+use common::raster::{rasterize, Textures};
+use common::FRAME_TIME;
+use egui_widget_texicon_demo_app::animation::AnimationSettings;
+use egui_widget_texicon_demo_app::menu_file::Menus;
+use egui_widget_texicon_demo_app::texi_menu::{MyTexicon, TexiMenu};
+use egui_widget_themenator::ThemeVariant;
+
+// === Constants ===
+#[rustfmt::skip] const UPDATE_ENV: &str         = "UPDATE_SNAPSHOTS";
+// A pixel differs if any channel is further off than this
+#[rustfmt::skip] const CHANNEL_TOLERANCE: u8    = 16;
+// ...and a snapshot fails if more than this fraction of its pixels differ
+#[rustfmt::skip] const MAX_DIFF_FRACTION: f64   = 0.002;
+#[rustfmt::skip] const MIN_FRAMES: usize        = 3;
+#[rustfmt::skip] const MAX_FRAMES: usize        = 50;
+
+const THEMES: [(&str, ThemeVariant); 4] = [
+    ("latte", ThemeVariant::Latte),
+    ("frappe", ThemeVariant::Frappe),
+    ("macchiato", ThemeVariant::Macchiato),
+    ("mocha", ThemeVariant::Mocha),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Normal,
+    Hovered,
+    Selected,
+    Disabled,
+}
+
+const STATES: [State; 4] = [
+    State::Normal,
+    State::Hovered,
+    State::Selected,
+    State::Disabled,
+];
+
+struct MenuCase {
+    name: &'static str,
+    size: egui::Vec2,
+    items: fn(Menus) -> Vec<MyTexicon>,
+    build: fn(Vec<MyTexicon>) -> TexiMenu,
+}
+
+// Draw the menu alone until its images have loaded, and rasterise the last frame
+fn render(case: &MenuCase, theme: ThemeVariant, state: State) -> image::RgbaImage {
+    let ctx = egui::Context::default();
+    egui_extras::install_image_loaders(&ctx);
+    ThemeVariant::set_current(&ctx, theme);
+    // Snapshots show where the hover and selection animations end up
+    AnimationSettings {
+        reduce_motion: true,
+        ..AnimationSettings::default()
+    }
+    .set(&ctx);
+
+    let mut items = (case.items)(Menus::builtin());
+    if state == State::Disabled {
+        for item in &mut items {
+            item.enabled = false;
+        }
+    }
+    let mut menu = (case.build)(items);
+    if state == State::Selected {
+        menu.set_selected_texicon(0);
+    }
+
+    let screen_rect = egui::Rect::from_min_size(egui::Pos2::ZERO, case.size);
+    let mut textures = Textures::default();
+    let mut output = None;
+    for frame in 0..MAX_FRAMES {
+        let mut raw_input = egui::RawInput {
+            screen_rect: Some(screen_rect),
+            time: Some(frame as f64 * FRAME_TIME),
+            ..Default::default()
+        };
+        // Hover the first texicon once we know where it is
+        if let (State::Hovered, Some(rect)) = (state, menu.item_rect(0)) {
+            raw_input
+                .events
+                .push(egui::Event::PointerMoved(rect.center()));
+        }
+        let frame_output = ctx.run_ui(raw_input, |ui| {
+            egui::CentralPanel::default().show(ui, |ui| menu.draw_texicons(ui));
+        });
+        textures.update(&frame_output.textures_delta);
+        output = Some(frame_output);
+        if frame + 1 >= MIN_FRAMES && !ctx.has_pending_images() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    let output = output.expect("at least one frame was run");
+    let primitives = ctx.tessellate(output.shapes, output.pixels_per_point);
+    let size = (case.size * output.pixels_per_point).round();
+    rasterize(
+        &primitives,
+        &textures,
+        [size.x as u32, size.y as u32],
+        output.pixels_per_point,
+        egui::Color32::BLACK,
+    )
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{name}.png"))
+}
+
+// Compare with the golden image, or record it if updates were asked for
+fn check(name: &str, image: &image::RgbaImage) -> Result<(), String> {
+    let path = snapshot_path(name);
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        return image
+            .save(&path)
+            .map_err(|err| format!("{name}: can't save {}: {err}", path.display()));
+    }
+    if !path.exists() {
+        let new_path = path.with_extension("new.png");
+        image.save(&new_path).ok();
+        return Err(format!(
+            "{name}: no snapshot at {}, see {} and record it with {UPDATE_ENV}=1",
+            path.display(),
+            new_path.display()
+        ));
+    }
+
+    let golden = image::open(&path)
+        .map_err(|err| format!("{name}: can't read {}: {err}", path.display()))?
+        .to_rgba8();
+    if golden.dimensions() != image.dimensions() {
+        return Err(format!(
+            "{name}: size {:?}, expected {:?}",
+            image.dimensions(),
+            golden.dimensions()
+        ));
+    }
+
+    let differing = image
+        .pixels()
+        .zip(golden.pixels())
+        .filter(|(new, old)| {
+            new.0
+                .iter()
+                .zip(old.0)
+                .any(|(&a, b)| a.abs_diff(b) > CHANNEL_TOLERANCE)
+        })
+        .count();
+    let allowed = (f64::from(image.width() * image.height()) * MAX_DIFF_FRACTION) as usize;
+    if differing > allowed {
+        let new_path = path.with_extension("new.png");
+        image.save(&new_path).ok();
+        return Err(format!(
+            "{name}: {differing} pixels differ (at most {allowed} may), see {}",
+            new_path.display()
+        ));
+    }
+    Ok(())
+}
+
+fn check_menu(case: &MenuCase) {
+    let failures: Vec<String> = THEMES
+        .iter()
+        .flat_map(|theme| STATES.iter().map(move |&state| (theme, state)))
+        .filter_map(|((theme_name, theme), state)| {
+            let name = format!("{}_{theme_name}_{state:?}", case.name).to_lowercase();
+            check(&name, &render(case, *theme, state)).err()
+        })
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "snapshots differ:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn top_menu() {
+    check_menu(&MenuCase {
+        name: "top",
+        size: egui::vec2(800.0, 150.0),
+        items: |menus| menus.top,
+        build: egui_widget_texicon_demo_app::texi_top_menu::new_menu,
+    });
+}
+
+#[test]
+fn side_menu() {
+    check_menu(&MenuCase {
+        name: "side",
+        size: egui::vec2(150.0, 500.0),
+        items: |menus| menus.side,
+        build: egui_widget_texicon_demo_app::texi_side_menu::new_menu,
+    });
+}
+
+#[test]
+fn central_menu() {
+    check_menu(&MenuCase {
+        name: "central",
+        size: egui::vec2(800.0, 220.0),
+        items: |menus| menus.central,
+        build: egui_widget_texicon_demo_app::texi_central_menu::new_menu,
+    });
+}
@@ -0,0 +1,50 @@
+// Runs the stress scene headless, the way CI does, and checks the command line.
+
+use egui_widget_texicon_demo_app::stress::{run_headless, StressArgs, MAX_TEXICONS};
+
+fn args(line: &str) -> Result<StressArgs, String> {
+    StressArgs::parse(line.split_whitespace().map(str::to_owned))
+}
+
+#[test]
+fn headless_run_times_every_frame() {
+    let ctx = egui::Context::default();
+    egui_extras::install_image_loaders(&ctx);
+
+    let report = run_headless(&ctx, 200, 3, egui::vec2(1400.0, 900.0));
+
+    assert_eq!(report.texicons, 200, "texicon count");
+    assert_eq!(report.layout.total_count(), 3, "one layout time per frame");
+    assert_eq!(
+        report.tessellation.total_count(),
+        3,
+        "one tessellation time per frame"
+    );
+    assert!(report.primitives > 0, "the scene tessellates to meshes");
+    assert!(
+        report.to_json().contains("\"Tessellation\""),
+        "the report names its timers"
+    );
+}
+
+#[test]
+fn command_line() {
+    assert_eq!(args(""), Ok(StressArgs::default()), "no arguments");
+    assert_eq!(
+        args("--stress 500 --headless --frames 20"),
+        Ok(StressArgs {
+            count: Some(500),
+            headless: true,
+            frames: Some(20),
+        }),
+        "headless run"
+    );
+    assert_eq!(
+        args("--stress 1000000").map(|args| args.count),
+        Ok(Some(MAX_TEXICONS)),
+        "count is clamped"
+    );
+    assert!(args("--headless").is_err(), "headless needs a count");
+    assert!(args("--stress lots").is_err(), "count must be a number");
+    assert!(args("--fast").is_err(), "unknown argument");
+}